@@ -13,7 +13,7 @@ pub struct Header {}
 )]
 pub struct SoapEnvelope<BODY>
 where
-  BODY: YaSerialize + YaDeserialize + Default,
+  BODY: YaSerialize + YaDeserializeOwned + Default,
 {
   #[yaserde(rename = "encodingStyle", prefix = "s", attribute = true)]
   pub encoding_style: String,
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 mod bbigras_namespace;
 mod boscop;
 mod generic;
@@ -0,0 +1,157 @@
+//! Deserialize a large namespaced document with the generated code, and
+//! compare the loop of the generated deserializers when it clones each
+//! peeked event, as it used to, with the one inspecting it by reference.
+
+#[macro_use]
+extern crate yaserde_derive;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use yaserde::de::Deserializer;
+use yaserde::events::ReadEvent;
+
+#[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+#[yaserde(
+  rename = "catalog",
+  prefix = "c",
+  namespaces = {
+    "c" = "http://example.com/catalog",
+    "m" = "http://example.com/media",
+  }
+)]
+pub struct Catalog {
+  #[yaserde(prefix = "c")]
+  item: Vec<Item>,
+}
+
+#[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+#[yaserde(
+  prefix = "c",
+  namespaces = {
+    "c" = "http://example.com/catalog",
+    "m" = "http://example.com/media",
+  }
+)]
+pub struct Item {
+  #[yaserde(attribute = true)]
+  id: u32,
+  #[yaserde(attribute = true)]
+  sku: String,
+  #[yaserde(attribute = true)]
+  currency: String,
+  #[yaserde(prefix = "c")]
+  name: String,
+  #[yaserde(prefix = "c")]
+  price: f64,
+  #[yaserde(prefix = "m")]
+  image: Vec<Image>,
+}
+
+#[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+#[yaserde(
+  prefix = "m",
+  namespaces = {
+    "m" = "http://example.com/media",
+  }
+)]
+pub struct Image {
+  #[yaserde(attribute = true)]
+  width: u32,
+  #[yaserde(attribute = true)]
+  height: u32,
+  #[yaserde(prefix = "m")]
+  url: String,
+}
+
+fn document(count: u32) -> String {
+  let catalog = Catalog {
+    item: (0..count)
+      .map(|id| Item {
+        id,
+        sku: format!("SKU-{id:08}"),
+        currency: "EUR".to_string(),
+        name: format!("Item number {id}"),
+        price: f64::from(id) / 4.0,
+        image: vec![
+          Image {
+            width: 640,
+            height: 480,
+            url: format!("https://example.com/images/{id}/large.png"),
+          },
+          Image {
+            width: 64,
+            height: 48,
+            url: format!("https://example.com/images/{id}/small.png"),
+          },
+        ],
+      })
+      .collect(),
+  };
+
+  yaserde::ser::to_string(&catalog).unwrap()
+}
+
+fn deserialize(c: &mut Criterion) {
+  let content = document(10_000);
+
+  let mut group = c.benchmark_group("deserialize");
+  group.throughput(Throughput::Bytes(content.len() as u64));
+  group.sample_size(20);
+
+  group.bench_function("large document", |b| {
+    b.iter(|| {
+      let catalog: Catalog = yaserde::de::from_str(&content).unwrap();
+      catalog
+    })
+  });
+
+  group.finish();
+}
+
+/// Count the `c:item` elements and their attributes, looking at each event
+/// before consuming it like the generated deserializers do
+fn walk(content: &str, clone_peeked: bool) -> usize {
+  let mut reader = Deserializer::new_from_reader(content.as_bytes());
+  let mut count = 0;
+
+  loop {
+    let owned;
+    let event = if clone_peeked {
+      owned = reader.peek().unwrap().to_owned();
+      &owned
+    } else {
+      reader.peek().unwrap()
+    };
+
+    match event {
+      ReadEvent::StartElement {
+        name, attributes, ..
+      } => {
+        let namespace = name.namespace.as_deref().unwrap_or_default();
+        if (namespace, name.local_name.as_str()) == ("http://example.com/catalog", "item") {
+          count += 1 + attributes.len();
+        }
+      }
+      ReadEvent::EndDocument => break,
+      _ => {}
+    }
+    reader.next_event().unwrap();
+  }
+
+  count
+}
+
+fn peeked_event(c: &mut Criterion) {
+  let content = document(10_000);
+
+  let mut group = c.benchmark_group("peeked event");
+  group.throughput(Throughput::Bytes(content.len() as u64));
+  group.sample_size(20);
+
+  group.bench_function("cloned", |b| b.iter(|| walk(&content, true)));
+  group.bench_function("by reference", |b| b.iter(|| walk(&content, false)));
+
+  group.finish();
+}
+
+criterion_group!(benches, deserialize, peeked_event);
+criterion_main!(benches);
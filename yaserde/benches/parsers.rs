@@ -0,0 +1,62 @@
+#[macro_use]
+extern crate yaserde_derive;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use yaserde::de::{from_reader_with_parser, Parser};
+
+#[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+#[yaserde(rename = "records")]
+pub struct Records {
+  record: Vec<Record>,
+}
+
+#[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+#[yaserde(rename = "record")]
+pub struct Record {
+  #[yaserde(attribute = true)]
+  id: u32,
+  #[yaserde(attribute = true)]
+  enabled: bool,
+  name: String,
+  description: String,
+  tags: Vec<String>,
+  score: f64,
+}
+
+fn document(count: u32) -> String {
+  let records = Records {
+    record: (0..count)
+      .map(|id| Record {
+        id,
+        enabled: id % 2 == 0,
+        name: format!("record {id}"),
+        description: format!("A description of record {id} & its <content>"),
+        tags: vec!["first".to_string(), "second".to_string()],
+        score: f64::from(id) / 3.0,
+      })
+      .collect(),
+  };
+
+  yaserde::ser::to_string(&records).unwrap()
+}
+
+fn deserialize(c: &mut Criterion) {
+  let content = document(10_000);
+
+  let mut group = c.benchmark_group("deserialize");
+  group.throughput(Throughput::Bytes(content.len() as u64));
+
+  for (name, parser) in [("xml-rs", Parser::XmlRs), ("quick-xml", Parser::QuickXml)] {
+    group.bench_function(name, |b| {
+      b.iter(|| {
+        let records: Records = from_reader_with_parser(content.as_bytes(), parser).unwrap();
+        records
+      })
+    });
+  }
+
+  group.finish();
+}
+
+criterion_group!(benches, deserialize);
+criterion_main!(benches);
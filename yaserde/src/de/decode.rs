@@ -0,0 +1,145 @@
+//! Decode documents to UTF-8 with encoding_rs, from their byte order mark
+//! or the encoding of their declaration.
+
+use encoding_rs::{Decoder, Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use std::io::{Read, Result};
+
+/// Longest prefix read to find the declaration
+const SNIFF_LENGTH: usize = 1024;
+const CHUNK_LENGTH: usize = 8192;
+
+pub(crate) struct DecodeReader<R: Read> {
+  inner: R,
+  /// Bytes read from `inner` and not returned or decoded yet
+  input: Vec<u8>,
+  /// Decoded bytes, returned from `position`
+  output: Vec<u8>,
+  position: usize,
+  /// `None` once sniffed when the document is in UTF-8
+  decoder: Option<Decoder>,
+  sniffed: bool,
+  finished: bool,
+}
+
+impl<R: Read> DecodeReader<R> {
+  pub(crate) fn new(inner: R) -> Self {
+    DecodeReader {
+      inner,
+      input: vec![],
+      output: vec![],
+      position: 0,
+      decoder: None,
+      sniffed: false,
+      finished: false,
+    }
+  }
+
+  fn sniff(&mut self) -> Result<()> {
+    let mut chunk = [0; SNIFF_LENGTH];
+    while self.input.len() < SNIFF_LENGTH && find(&self.input, b"?>").is_none() {
+      let read = self
+        .inner
+        .read(&mut chunk[..SNIFF_LENGTH - self.input.len()])?;
+      if read == 0 {
+        break;
+      }
+      self.input.extend_from_slice(&chunk[..read]);
+    }
+
+    self.sniffed = true;
+    self.decoder = sniff(&self.input)
+      .filter(|encoding| *encoding != UTF_8)
+      .map(Encoding::new_decoder_with_bom_removal);
+    Ok(())
+  }
+
+  /// Decode the next chunk, leaving `output` empty at the end of the document
+  fn decode(&mut self) -> Result<()> {
+    let decoder = match &mut self.decoder {
+      Some(decoder) => decoder,
+      None => return Ok(()),
+    };
+
+    if self.input.is_empty() {
+      let mut chunk = [0; CHUNK_LENGTH];
+      let read = self.inner.read(&mut chunk)?;
+      self.input.extend_from_slice(&chunk[..read]);
+    }
+
+    let last = self.input.is_empty();
+    let mut output = String::with_capacity(
+      decoder
+        .max_utf8_buffer_length(self.input.len())
+        .unwrap_or(self.input.len() * 3 + 16),
+    );
+    let (_result, read, _replaced) = decoder.decode_to_string(&self.input, &mut output, last);
+
+    self.input.drain(..read);
+    self.output = output.into_bytes();
+    self.position = 0;
+    self.finished = last;
+    Ok(())
+  }
+}
+
+impl<R: Read> Read for DecodeReader<R> {
+  fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+    if !self.sniffed {
+      self.sniff()?;
+    }
+
+    if self.decoder.is_none() {
+      if self.input.is_empty() {
+        return self.inner.read(buf);
+      }
+      let length = buf.len().min(self.input.len());
+      buf[..length].copy_from_slice(&self.input[..length]);
+      self.input.drain(..length);
+      return Ok(length);
+    }
+
+    while self.position == self.output.len() {
+      if self.finished {
+        return Ok(0);
+      }
+      self.decode()?;
+    }
+
+    let length = buf.len().min(self.output.len() - self.position);
+    buf[..length].copy_from_slice(&self.output[self.position..self.position + length]);
+    self.position += length;
+    Ok(length)
+  }
+}
+
+/// Find the encoding from the byte order mark, then from the declaration
+fn sniff(prefix: &[u8]) -> Option<&'static Encoding> {
+  if let Some((encoding, _bom_length)) = Encoding::for_bom(prefix) {
+    return Some(encoding);
+  }
+
+  match prefix {
+    [b'<', 0, b'?', 0, ..] => return Some(UTF_16LE),
+    [0, b'<', 0, b'?', ..] => return Some(UTF_16BE),
+    _ => {}
+  }
+
+  // The declaration is in ASCII for ASCII compatible encodings
+  let end = find(prefix, b"?>")?;
+  let declaration = std::str::from_utf8(&prefix[..end]).ok()?;
+  let (_, encoding) = declaration.strip_prefix("<?xml")?.split_once("encoding")?;
+  let encoding = encoding.trim_start().strip_prefix('=')?.trim_start();
+  let quote = encoding
+    .chars()
+    .next()
+    .filter(|c| *c == '"' || *c == '\'')?;
+  let (label, _) = encoding[1..].split_once(quote)?;
+
+  Encoding::for_label(label.as_bytes())
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+  haystack
+    .windows(needle.len())
+    .position(|window| window == needle)
+}
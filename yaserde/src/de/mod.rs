@@ -1,86 +1,668 @@
 //! Generic data structure deserialization framework.
 //!
 
-use crate::YaDeserialize;
+use crate::events::{Declaration, OwnedName, Prolog, ReadEvent as XmlEvent, XmlVersion};
+use crate::{ProcessingInstruction, YaDeserialize, YaDeserializeOwned};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
 use std::io::Read;
-use xml::name::OwnedName;
-use xml::reader::{EventReader, ParserConfig, XmlEvent};
+use std::marker::PhantomData;
+use xml::common::{Position, TextPosition};
+use xml::namespace::{Namespace, NS_XML_URI};
+use xml::reader::{EventReader, ParserConfig, ParserConfig2};
 
-pub fn from_str<T: YaDeserialize>(s: &str) -> Result<T, String> {
-  from_reader(s.as_bytes())
+#[cfg(feature = "encoding_rs")]
+mod decode;
+#[cfg(feature = "quick-xml")]
+mod quick_xml_reader;
+mod text_limit;
+
+use text_limit::TextLimit;
+
+pub fn from_str<'de, T: YaDeserialize<'de>>(s: &'de str) -> Result<T, String> {
+  from_str_with_parser(s, Parser::default())
+}
+
+/// Deserialize with options, failing with `Error::LimitExceeded` when the
+/// document is over one of its limits
+pub fn from_str_with_config<'de, T: YaDeserialize<'de>>(
+  s: &'de str,
+  config: &Config,
+) -> Result<T, Error> {
+  Deserializer::new_from_str_with_config(s, config).deserialize()
+}
+
+/// Deserialize a flattened field from the elements its parent has no field
+/// for, which also holds those of the other flattened fields
+#[doc(hidden)]
+pub fn from_flattened_str<'de, T: YaDeserialize<'de>>(s: &'de str) -> Result<T, String> {
+  let config = Config {
+    unknown_elements: UnknownElements::Skip,
+    ..Default::default()
+  };
+  from_str_with_config(s, &config).map_err(|error| error.to_string())
+}
+
+/// Deserialize with the chosen XML parser. With quick-xml, text and
+/// attribute values borrow from `s` unless they contain escapes.
+pub fn from_str_with_parser<'de, T: YaDeserialize<'de>>(
+  s: &'de str,
+  parser: Parser,
+) -> Result<T, String> {
+  <T as YaDeserialize>::deserialize(&mut Deserializer::new_from_str_with_parser(s, parser))
+}
+
+pub fn from_reader<R: Read, T: YaDeserializeOwned>(reader: R) -> Result<T, String> {
+  from_reader_with_parser(reader, Parser::default())
+}
+
+/// Deserialize with the chosen XML parser
+pub fn from_reader_with_parser<R: Read, T: YaDeserializeOwned>(
+  reader: R,
+  parser: Parser,
+) -> Result<T, String> {
+  <T as YaDeserialize>::deserialize(&mut Deserializer::new_from_reader_with_parser(
+    reader, parser,
+  ))
+}
+
+/// Deserialize with options, failing with `Error::LimitExceeded` when the
+/// document is over one of its limits
+pub fn from_reader_with_config<R: Read, T: YaDeserializeOwned>(
+  reader: R,
+  config: &Config,
+) -> Result<T, Error> {
+  Deserializer::new_from_reader_with_config(reader, config).deserialize()
+}
+
+/// Deserialize from an async reader without blocking the executor.
+///
+/// The reader is bridged to `std::io::Read` and parsed by the synchronous
+/// parser on the blocking thread pool while the document is being received,
+/// so each call holds a blocking thread for the whole transfer. It must be
+/// called within a Tokio runtime, and panics otherwise.
+#[cfg(feature = "tokio")]
+pub async fn from_async_reader<R, T>(reader: R) -> Result<T, String>
+where
+  R: tokio::io::AsyncRead + Unpin + Send + 'static,
+  T: YaDeserializeOwned + Send + 'static,
+{
+  let reader = tokio_util::io::SyncIoBridge::new(reader);
+
+  tokio::task::spawn_blocking(move || from_reader(reader))
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Deserialize from an async reader with options, on a blocking thread like
+/// `from_async_reader`
+#[cfg(feature = "tokio")]
+pub async fn from_async_reader_with_config<R, T>(reader: R, config: &Config) -> Result<T, Error>
+where
+  R: tokio::io::AsyncRead + Unpin + Send + 'static,
+  T: YaDeserializeOwned + Send + 'static,
+{
+  let reader = tokio_util::io::SyncIoBridge::new(reader);
+  let config = config.clone();
+
+  tokio::task::spawn_blocking(move || from_reader_with_config(reader, &config))
+    .await
+    .map_err(|e| Error::Message(e.to_string()))?
+}
+
+/// Split a qualified name found in text or in an attribute value, like
+/// `p:Name`, with the URI `p` is bound to in `namespace`. Unprefixed names
+/// have no namespace, names with an unbound prefix are returned whole.
+pub fn resolve_qname<'a>(value: &'a str, namespace: &'a Namespace) -> (Option<&'a str>, &'a str) {
+  match value.split_once(':') {
+    Some((prefix, local_name)) => match namespace.get(prefix) {
+      Some(uri) => (Some(uri), local_name),
+      None => (None, value),
+    },
+    None => (None, value),
+  }
+}
+
+/// The XML parser reading the events. Documents are always written with
+/// xml-rs, whatever parser they were read with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Parser {
+  #[default]
+  XmlRs,
+  #[cfg(feature = "quick-xml")]
+  QuickXml,
 }
 
-pub fn from_reader<R: Read, T: YaDeserialize>(reader: R) -> Result<T, String> {
-  <T as YaDeserialize>::deserialize(&mut Deserializer::new_from_reader(reader))
+/// Options of the deserializer
+#[derive(Clone, Debug)]
+pub struct Config {
+  pub parser: Parser,
+  /// Keep the comments before each element for `#[yaserde(comments = true)]` fields
+  pub comments: bool,
+  /// Trim text and skip whitespace between elements, except inside
+  /// elements with `xml:space="preserve"`
+  pub trim_whitespace: bool,
+  /// Read CDATA sections as text. Otherwise they are read as
+  /// `ReadEvent::CData`, which `RawXml` writes back as CDATA.
+  pub cdata_to_characters: bool,
+  /// Longest text an entity declared in the DTD expands to, with xml-rs
+  pub max_entity_expansion_length: usize,
+  /// How many times entities expand into other entities, with xml-rs
+  pub max_entity_expansion_depth: u8,
+  /// Deepest element nesting accepted
+  pub max_depth: Option<usize>,
+  /// Most events read from the document, comments and whitespace included
+  pub max_events: Option<usize>,
+  /// Longest text or CDATA section accepted, in bytes. It is checked while
+  /// the document is read, so longer text is never held in memory.
+  pub max_text_size: Option<usize>,
+  /// Most attributes accepted on an element, namespace declarations excluded
+  pub max_attributes: Option<usize>,
+  /// What derived structs do with elements they have no field for
+  pub unknown_elements: UnknownElements,
+  /// Namespace URIs accepted in place of those declared with
+  /// `#[yaserde(namespaces)]`, as alternate URI to declared URI, like
+  /// both versions of a schema read with the same types
+  pub namespace_aliases: BTreeMap<String, String>,
 }
 
-pub struct Deserializer<R: Read> {
+impl Default for Config {
+  fn default() -> Self {
+    Config {
+      parser: Parser::default(),
+      comments: false,
+      trim_whitespace: true,
+      cdata_to_characters: true,
+      max_entity_expansion_length: 1_000_000,
+      max_entity_expansion_depth: 10,
+      max_depth: None,
+      max_events: None,
+      max_text_size: None,
+      max_attributes: None,
+      unknown_elements: UnknownElements::default(),
+      namespace_aliases: BTreeMap::new(),
+    }
+  }
+}
+
+/// Policy for the elements a struct has no field for
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnknownElements {
+  /// Fail with `Found unauthorized element`
+  #[default]
+  Error,
+  /// Skip them with their content
+  Skip,
+}
+
+/// A limit of `Config` exceeded by a document, with its maximum
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Limit {
+  Depth(usize),
+  Events(usize),
+  TextSize(usize),
+  Attributes(usize),
+}
+
+impl fmt::Display for Limit {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Limit::Depth(max) => write!(f, "Elements are nested deeper than {}", max),
+      Limit::Events(max) => write!(f, "Document has more than {} events", max),
+      Limit::TextSize(max) => write!(f, "Text is longer than {} bytes", max),
+      Limit::Attributes(max) => write!(f, "Element has more than {} attributes", max),
+    }
+  }
+}
+
+/// Error of `from_str_with_config` and `from_reader_with_config`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+  LimitExceeded(Limit),
+  /// Any other error, as returned by `YaDeserialize`
+  Message(String),
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Error::LimitExceeded(limit) => limit.fmt(f),
+      Error::Message(message) => f.write_str(message),
+    }
+  }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for String {
+  fn from(error: Error) -> Self {
+    error.to_string()
+  }
+}
+
+#[allow(clippy::large_enum_variant)]
+enum EventSource<'de, R: Read> {
+  /// Parser given to `Deserializer::new`
+  XmlRs(EventReader<R>),
+  LimitedXmlRs(EventReader<TextLimit<R>>),
+  /// Documents in other encodings than UTF-8 are decoded first
+  #[cfg(feature = "encoding_rs")]
+  DecodedXmlRs(EventReader<TextLimit<decode::DecodeReader<R>>>),
+  #[cfg(feature = "quick-xml")]
+  QuickXml(quick_xml_reader::QuickXmlReader<'de, R>),
+  /// Only there to use `'de` when no parser borrows from the input
+  #[cfg(not(feature = "quick-xml"))]
+  #[allow(dead_code)]
+  Unused(std::convert::Infallible, PhantomData<&'de str>),
+}
+
+impl<R: Read> EventSource<'_, R> {
+  /// Whether the source stopped on a text larger than `Config::max_text_size`
+  fn text_size_exceeded(&self) -> bool {
+    match self {
+      EventSource::XmlRs(_) => false,
+      EventSource::LimitedXmlRs(reader) => reader.source().exceeded(),
+      #[cfg(feature = "encoding_rs")]
+      EventSource::DecodedXmlRs(reader) => reader.source().exceeded(),
+      #[cfg(feature = "quick-xml")]
+      EventSource::QuickXml(reader) => reader.text_size_exceeded(),
+      #[cfg(not(feature = "quick-xml"))]
+      EventSource::Unused(never, _) => match *never {},
+    }
+  }
+}
+
+pub struct Deserializer<'de, R: Read> {
   depth: usize,
-  reader: EventReader<R>,
-  peeked: Option<XmlEvent>,
+  source: EventSource<'de, R>,
+  /// Peeked events, with whether they close a self-closed element
+  peeked: VecDeque<(XmlEvent<'de>, bool)>,
+  /// Position of the last xml-rs `StartElement`, shared by the `EndElement` of `<a/>`
+  start_position: Option<TextPosition>,
+  declaration: Option<Declaration>,
+  /// Processing instructions read before the root element
+  prolog: Vec<Prolog>,
+  in_prolog: bool,
+  /// Depth of the events read from the source, ahead of `depth` when peeking
+  read_depth: usize,
+  /// Depths where processing instructions are returned rather than skipped
+  processing_instruction_depths: Vec<usize>,
+  comments: bool,
+  /// Comments read since the last element
+  pending_comments: Vec<String>,
+  /// Comments before each element read and not consumed yet
+  element_comments: VecDeque<Vec<String>>,
+  /// Comments before the last element consumed
+  leading_comments: Vec<String>,
+  trim_whitespace: bool,
+  /// Whether whitespace is preserved in each element read and not ended
+  preserve_space: Vec<bool>,
+  max_depth: Option<usize>,
+  max_events: Option<usize>,
+  max_text_size: Option<usize>,
+  max_attributes: Option<usize>,
+  /// Events read from the source
+  events: usize,
+  unknown_elements: UnknownElements,
+  /// Returned again once exceeded, like parser errors
+  limit_exceeded: Option<Limit>,
+  /// First prefix bound to each namespace URI
+  prefixes: BTreeMap<String, String>,
+  namespace_aliases: BTreeMap<String, String>,
 }
 
-impl<R: Read> Deserializer<R> {
+impl<'de> Deserializer<'de, &'de [u8]> {
+  /// Read from a string, which text and attribute values can be borrowed from
+  pub fn new_from_str_with_parser(s: &'de str, parser: Parser) -> Self {
+    Self::new_from_str_with_config(
+      s,
+      &Config {
+        parser,
+        ..Default::default()
+      },
+    )
+  }
+
+  pub fn new_from_str_with_config(s: &'de str, config: &Config) -> Self {
+    let source = match config.parser {
+      #[cfg(feature = "quick-xml")]
+      Parser::QuickXml => EventSource::QuickXml(quick_xml_reader::QuickXmlReader::from_str(
+        s,
+        config.cdata_to_characters,
+        config.max_text_size,
+      )),
+      // The string is already decoded, whatever its declaration says
+      _ => EventSource::LimitedXmlRs(EventReader::new_with_config(
+        TextLimit::new(s.as_bytes(), config.max_text_size),
+        xml_rs_config(config)
+          .override_encoding(Some(xml::Encoding::Utf8))
+          .ignore_invalid_encoding_declarations(true),
+      )),
+    };
+
+    Self::new_from_source(source, config)
+  }
+}
+
+impl<'de, R: Read> Deserializer<'de, R> {
+  /// Read from an xml-rs parser, which configuration trims whitespace or not
   pub fn new(reader: EventReader<R>) -> Self {
+    let config = Config {
+      trim_whitespace: false,
+      ..Default::default()
+    };
+
+    Self::new_from_source(EventSource::XmlRs(reader), &config)
+  }
+
+  fn new_from_source(source: EventSource<'de, R>, config: &Config) -> Self {
     Deserializer {
       depth: 0,
-      reader,
-      peeked: None,
+      source,
+      peeked: VecDeque::new(),
+      start_position: None,
+      declaration: None,
+      prolog: vec![],
+      in_prolog: true,
+      read_depth: 0,
+      processing_instruction_depths: vec![],
+      comments: config.comments,
+      pending_comments: vec![],
+      element_comments: VecDeque::new(),
+      leading_comments: vec![],
+      trim_whitespace: config.trim_whitespace,
+      preserve_space: vec![],
+      max_depth: config.max_depth,
+      max_events: config.max_events,
+      max_text_size: config.max_text_size,
+      max_attributes: config.max_attributes,
+      events: 0,
+      unknown_elements: config.unknown_elements,
+      limit_exceeded: None,
+      prefixes: BTreeMap::new(),
+      namespace_aliases: config.namespace_aliases.clone(),
     }
   }
 
   pub fn new_from_reader(reader: R) -> Self {
-    let config = ParserConfig::new()
-      .trim_whitespace(true)
-      .whitespace_to_characters(true)
-      .cdata_to_characters(true)
-      .ignore_comments(true)
-      .coalesce_characters(true);
+    Self::new_from_reader_with_parser(reader, Parser::default())
+  }
+
+  /// Read from `reader`. With the `encoding_rs` feature, documents in other
+  /// encodings than UTF-8 are decoded from their byte order mark or declaration.
+  pub fn new_from_reader_with_parser(reader: R, parser: Parser) -> Self {
+    Self::new_from_reader_with_config(
+      reader,
+      &Config {
+        parser,
+        ..Default::default()
+      },
+    )
+  }
+
+  pub fn new_from_reader_with_config(reader: R, config: &Config) -> Self {
+    let source = match config.parser {
+      #[cfg(feature = "encoding_rs")]
+      Parser::XmlRs => EventSource::DecodedXmlRs(EventReader::new_with_config(
+        TextLimit::new(decode::DecodeReader::new(reader), config.max_text_size),
+        xml_rs_config(config)
+          .override_encoding(Some(xml::Encoding::Utf8))
+          .ignore_invalid_encoding_declarations(true),
+      )),
+      #[cfg(not(feature = "encoding_rs"))]
+      Parser::XmlRs => EventSource::LimitedXmlRs(EventReader::new_with_config(
+        TextLimit::new(reader, config.max_text_size),
+        xml_rs_config(config),
+      )),
+      #[cfg(feature = "quick-xml")]
+      Parser::QuickXml => EventSource::QuickXml(quick_xml_reader::QuickXmlReader::new(
+        reader,
+        config.cdata_to_characters,
+        config.max_text_size,
+      )),
+    };
+
+    Self::new_from_source(source, config)
+  }
 
-    Self::new(EventReader::new_with_config(reader, config))
+  pub fn peek(&mut self) -> Result<&XmlEvent<'de>, String> {
+    self.peek_nth(0)
   }
 
-  pub fn peek(&mut self) -> Result<&XmlEvent, String> {
-    if self.peeked.is_none() {
-      self.peeked = Some(self.inner_next()?);
+  /// Look at the event `n` positions ahead without consuming anything
+  pub fn peek_nth(&mut self, n: usize) -> Result<&XmlEvent<'de>, String> {
+    while self.peeked.len() <= n {
+      let next = self.read_next()?;
+      self.peeked.push_back(next);
     }
 
-    if let Some(ref next) = self.peeked {
-      Ok(next)
-    } else {
-      Err("unable to peek next item".into())
+    self
+      .peeked
+      .get(n)
+      .map(|(event, _)| event)
+      .ok_or_else(|| "unable to peek next item".into())
+  }
+
+  /// Whether the next event is the end of an element written as `<a/>`
+  pub fn peek_self_closed(&mut self) -> Result<bool, String> {
+    self.peek()?;
+    Ok(self.peeked[0].1)
+  }
+
+  /// Name of the element about to start, looked at without cloning the event
+  pub fn peek_name(&mut self) -> Result<Option<&OwnedName>, String> {
+    match self.peek()? {
+      XmlEvent::StartElement { name, .. } => Ok(Some(name)),
+      _ => Ok(None),
     }
   }
 
-  pub fn inner_next(&mut self) -> Result<XmlEvent, String> {
+  pub fn inner_next(&mut self) -> Result<XmlEvent<'de>, String> {
+    let (event, _) = self.read_next()?;
+    if let XmlEvent::StartElement { .. } = event {
+      self.element_comments.pop_front();
+    }
+    Ok(event)
+  }
+
+  fn read_next(&mut self) -> Result<(XmlEvent<'de>, bool), String> {
+    if let Some(limit) = self.limit_exceeded {
+      return Err(limit.to_string());
+    }
+
     loop {
-      match self.reader.next() {
-        Ok(next) => {
-          match next {
-            XmlEvent::StartDocument { .. }
-            | XmlEvent::ProcessingInstruction { .. }
-            | XmlEvent::Comment(_) => { /* skip */ }
-            other => return Ok(other),
+      let next = match self.source {
+        EventSource::XmlRs(ref mut reader) => xml_rs_next(reader, &mut self.start_position),
+        EventSource::LimitedXmlRs(ref mut reader) => xml_rs_next(reader, &mut self.start_position),
+        #[cfg(feature = "encoding_rs")]
+        EventSource::DecodedXmlRs(ref mut reader) => xml_rs_next(reader, &mut self.start_position),
+        #[cfg(feature = "quick-xml")]
+        EventSource::QuickXml(ref mut reader) => {
+          reader.next().map(|next| (next, reader.self_closed()))
+        }
+        #[cfg(not(feature = "quick-xml"))]
+        EventSource::Unused(never, _) => match never {},
+      };
+      let (mut next, self_closed) = match next {
+        Ok(next) => next,
+        Err(_) if self.source.text_size_exceeded() => {
+          let limit = Limit::TextSize(self.max_text_size.unwrap_or_default());
+          self.limit_exceeded = Some(limit);
+          return Err(limit.to_string());
+        }
+        Err(error) => return Err(error),
+      };
+
+      if let Some(limit) = self.exceeded_limit(&next) {
+        self.limit_exceeded = Some(limit);
+        return Err(limit.to_string());
+      }
+      if !self.namespace_aliases.is_empty() {
+        self.unalias_namespaces(&mut next);
+      }
+
+      match next {
+        XmlEvent::StartDocument {
+          version,
+          encoding,
+          standalone,
+        } => {
+          self.declaration = Some(Declaration {
+            version: if version == "1.1" {
+              XmlVersion::Version11
+            } else {
+              XmlVersion::Version10
+            },
+            encoding: Some(encoding),
+            standalone,
+          });
+        }
+        XmlEvent::ProcessingInstruction { name, data } if self.in_prolog => {
+          self
+            .prolog
+            .push(Prolog::ProcessingInstruction(ProcessingInstruction {
+              name,
+              data,
+            }));
+        }
+        XmlEvent::ProcessingInstruction { .. }
+          if self
+            .processing_instruction_depths
+            .contains(&self.read_depth) =>
+        {
+          return Ok((next, self_closed))
+        }
+        XmlEvent::Comment(comment) if self.comments => {
+          self.pending_comments.push(comment.into_owned());
+        }
+        XmlEvent::ProcessingInstruction { .. } | XmlEvent::Comment(_) => { /* skip */ }
+        XmlEvent::Characters(text) if self.trim_whitespace && !self.preserves_space() => {
+          let text = trim(text);
+          if !text.is_empty() {
+            return Ok((XmlEvent::Characters(text), self_closed));
           }
         }
-        Err(msg) => {
-          return Err(msg.msg().to_string());
+        XmlEvent::StartElement {
+          ref attributes,
+          ref namespace,
+          ..
+        } => {
+          for (prefix, uri) in namespace {
+            if !uri.is_empty()
+              && prefix != "xml"
+              && prefix != "xmlns"
+              && !self.prefixes.contains_key(uri)
+            {
+              self.prefixes.insert(uri.to_string(), prefix.to_string());
+            }
+          }
+          let preserve_space = attributes
+            .iter()
+            .find(|attribute| {
+              attribute.name.local_name == "space"
+                && attribute.name.namespace.as_deref() == Some(NS_XML_URI)
+            })
+            .map_or_else(
+              || self.preserves_space(),
+              |attribute| attribute.value == "preserve",
+            );
+          self.preserve_space.push(preserve_space);
+          self.in_prolog = false;
+          self.read_depth += 1;
+          if self.comments {
+            let comments = std::mem::take(&mut self.pending_comments);
+            self.element_comments.push_back(comments);
+          }
+          return Ok((next, self_closed));
+        }
+        XmlEvent::EndElement { .. } => {
+          // Comments at the end of an element don't lead the next one
+          self.pending_comments.clear();
+          let depth = self.read_depth;
+          self
+            .processing_instruction_depths
+            .retain(|captured| *captured != depth);
+          self.read_depth = depth.saturating_sub(1);
+          self.preserve_space.pop();
+          return Ok((next, self_closed));
+        }
+        other => return Ok((other, self_closed)),
+      }
+    }
+  }
+
+  /// Count `event` and check it against the limits of the config
+  fn exceeded_limit(&mut self, event: &XmlEvent<'de>) -> Option<Limit> {
+    let over = |max: Option<usize>, value: usize| max.filter(|max| value > *max);
+
+    self.events += 1;
+    if let Some(max) = over(self.max_events, self.events) {
+      return Some(Limit::Events(max));
+    }
+
+    match event {
+      XmlEvent::StartElement { attributes, .. } => over(self.max_depth, self.read_depth + 1)
+        .map(Limit::Depth)
+        .or_else(|| over(self.max_attributes, attributes.len()).map(Limit::Attributes)),
+      XmlEvent::Characters(text) | XmlEvent::CData(text) | XmlEvent::Whitespace(text) => {
+        over(self.max_text_size, text.len()).map(Limit::TextSize)
+      }
+      _ => None,
+    }
+  }
+
+  /// Replace the alternate namespace URIs of `Config::namespace_aliases`
+  fn unalias_namespaces(&self, event: &mut XmlEvent<'de>) {
+    let unalias = |uri: &mut Option<String>| {
+      if let Some(declared) = uri.as_ref().and_then(|uri| self.namespace_aliases.get(uri)) {
+        *uri = Some(declared.clone());
+      }
+    };
+
+    match event {
+      XmlEvent::StartElement {
+        name,
+        attributes,
+        namespace,
+      } => {
+        unalias(&mut name.namespace);
+        for attribute in attributes {
+          unalias(&mut attribute.name.namespace);
+        }
+        let mut declared = Namespace::empty();
+        for (prefix, uri) in &*namespace {
+          declared.put(
+            prefix,
+            self.namespace_aliases.get(uri).map_or(uri, String::as_str),
+          );
         }
+        *namespace = declared;
       }
+      XmlEvent::EndElement { name } => unalias(&mut name.namespace),
+      _ => {}
     }
   }
 
-  pub fn next_event(&mut self) -> Result<XmlEvent, String> {
+  /// Whether `xml:space="preserve"` applies to the text read next
+  fn preserves_space(&self) -> bool {
+    self.preserve_space.last().copied().unwrap_or(false)
+  }
+
+  pub fn next_event(&mut self) -> Result<XmlEvent<'de>, String> {
     log::trace!("Calling Reader.next_event with depth :{}", self.depth);
-    let next_event = if let Some(peeked) = self.peeked.take() {
+    let (next_event, _) = if let Some(peeked) = self.peeked.pop_front() {
       peeked
     } else {
-      self.inner_next()?
+      self.read_next()?
     };
     match next_event {
       XmlEvent::StartElement { .. } => {
         log::trace!("increasing depth");
         self.depth += 1;
+        if self.comments {
+          self.leading_comments = self.element_comments.pop_front().unwrap_or_default();
+        }
       }
       XmlEvent::EndElement { .. } => {
         log::trace!("decreasing depth");
@@ -92,26 +674,86 @@ impl<R: Read> Deserializer<R> {
     Ok(next_event)
   }
 
-  pub fn skip_element(&mut self, mut _cb: impl FnMut(&XmlEvent)) -> Result<(), String> {
-    log::warn!("Skipping element skip_element");
-    return Err(format!(
-      "Skipping elemeent not allowed {:?}",
-      &self.next_event()
-    ));
-    // TODO make this optional. Maybe based on a feature flag?
-    // let depth = self.depth;
+  /// Skip the element about to start with its content, passing each event
+  /// to `cb`. Fails unless `Config::unknown_elements` is `UnknownElements::Skip`.
+  pub fn skip_element(&mut self, mut cb: impl FnMut(&XmlEvent<'de>)) -> Result<(), String> {
+    let name = match self.peek_name()? {
+      Some(name) => name.local_name.clone(),
+      None => return Err("Internal error: Bad Event".to_string()),
+    };
+    if self.unknown_elements == UnknownElements::Error {
+      return Err(format!("Found unauthorized element {}", name));
+    }
+
+    log::debug!("Skipping element {}", name);
+    let depth = self.depth;
+    cb(&self.next_event()?);
+    while self.depth > depth {
+      cb(&self.next_event()?);
+    }
 
-    // while self.depth >= depth {
-    //   cb(&self.next_event()?);
-    // }
+    Ok(())
+  }
 
-    // Ok(())
+  /// Iterate over the children of the element the reader is positioned on,
+  /// deserializing one `T` at a time.
+  pub fn iter<T: YaDeserialize<'de>>(&mut self) -> Iter<'_, 'de, R, T> {
+    Iter {
+      deserializer: self,
+      started: false,
+      done: false,
+      item: PhantomData,
+    }
   }
 
   pub fn depth(&self) -> usize {
     self.depth
   }
 
+  /// The limit of the config the document exceeded, once it is read
+  pub fn limit_exceeded(&self) -> Option<Limit> {
+    self.limit_exceeded
+  }
+
+  /// Deserialize a `T`, telling the limits exceeded from the other errors
+  pub fn deserialize<T: YaDeserialize<'de>>(&mut self) -> Result<T, Error> {
+    T::deserialize(self).map_err(|message| match self.limit_exceeded {
+      Some(limit) => Error::LimitExceeded(limit),
+      None => Error::Message(message),
+    })
+  }
+
+  /// The XML declaration of the document, once the events after it are read.
+  /// xml-rs reports the default one when the document has none.
+  pub fn declaration(&self) -> Option<&Declaration> {
+    self.declaration.as_ref()
+  }
+
+  /// The processing instructions before the root element, once it is read.
+  /// Both parsers skip the DOCTYPE.
+  pub fn prolog(&self) -> &[Prolog] {
+    &self.prolog
+  }
+
+  /// The prefix first bound to each namespace URI in the elements read, an
+  /// empty one for the default namespace. Give it to `ser::Config::prefixes`
+  /// to write the document back with the same prefixes.
+  pub fn prefixes(&self) -> &BTreeMap<String, String> {
+    &self.prefixes
+  }
+
+  /// Take the comments before the element just started, when the
+  /// deserializer keeps them with `Config::comments`
+  pub fn take_leading_comments(&mut self) -> Vec<String> {
+    std::mem::take(&mut self.leading_comments)
+  }
+
+  /// Return the processing instructions directly in the element just started
+  /// as events, until it ends. They are skipped otherwise.
+  pub fn capture_processing_instructions(&mut self) {
+    self.processing_instruction_depths.push(self.depth);
+  }
+
   pub fn read_inner_value<T, F: FnOnce(&mut Self) -> Result<T, String>>(
     &mut self,
     f: F,
@@ -140,3 +782,96 @@ impl<R: Read> Deserializer<R> {
     }
   }
 }
+
+/// Text is trimmed by the deserializer, which knows about `xml:space`
+fn xml_rs_config(config: &Config) -> ParserConfig2 {
+  ParserConfig::new()
+    .trim_whitespace(false)
+    .whitespace_to_characters(true)
+    .cdata_to_characters(config.cdata_to_characters)
+    .ignore_comments(!config.comments)
+    .coalesce_characters(true)
+    .max_entity_expansion_length(config.max_entity_expansion_length)
+    .max_entity_expansion_depth(config.max_entity_expansion_depth)
+}
+
+fn trim(text: Cow<'_, str>) -> Cow<'_, str> {
+  match text {
+    Cow::Borrowed(text) => Cow::Borrowed(text.trim()),
+    Cow::Owned(text) if text.trim().len() == text.len() => Cow::Owned(text),
+    Cow::Owned(text) => Cow::Owned(text.trim().to_string()),
+  }
+}
+
+/// Read the next xml-rs event, with whether it closes a self-closed element
+fn xml_rs_next<'de, S: Read>(
+  reader: &mut EventReader<S>,
+  start_position: &mut Option<TextPosition>,
+) -> Result<(XmlEvent<'de>, bool), String> {
+  let next = reader.next().map_err(|msg| msg.msg().to_string())?;
+  let position = Some(reader.position());
+  let self_closed =
+    matches!(next, xml::reader::XmlEvent::EndElement { .. }) && *start_position == position;
+
+  *start_position = match next {
+    xml::reader::XmlEvent::StartElement { .. } => position,
+    _ => None,
+  };
+  Ok((XmlEvent::from(next), self_closed))
+}
+
+/// Iterator returned by [`Deserializer::iter`].
+pub struct Iter<'a, 'de, R: Read, T> {
+  deserializer: &'a mut Deserializer<'de, R>,
+  started: bool,
+  done: bool,
+  item: PhantomData<T>,
+}
+
+impl<'de, R: Read, T: YaDeserialize<'de>> Iter<'_, 'de, R, T> {
+  fn next_item(&mut self) -> Result<Option<T>, String> {
+    if !self.started {
+      self.started = true;
+
+      // Enter the parent element
+      if let XmlEvent::StartElement { .. } = self.deserializer.peek()? {
+        self.deserializer.next_event()?;
+      } else {
+        return Err("Iterator should start on a StartElement".to_string());
+      }
+    }
+
+    loop {
+      match self.deserializer.peek()? {
+        XmlEvent::StartElement { name, .. } => {
+          let name = name.clone();
+          let value = T::deserialize(self.deserializer)?;
+          self.deserializer.expect_end_element(&name)?;
+          return Ok(Some(value));
+        }
+        XmlEvent::EndElement { .. } => {
+          self.deserializer.next_event()?;
+          return Ok(None);
+        }
+        XmlEvent::EndDocument => return Ok(None),
+        _ => {
+          self.deserializer.next_event()?;
+        }
+      }
+    }
+  }
+}
+
+impl<'de, R: Read, T: YaDeserialize<'de>> Iterator for Iter<'_, 'de, R, T> {
+  type Item = Result<T, String>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.done {
+      return None;
+    }
+
+    let item = self.next_item().transpose();
+    self.done = !matches!(item, Some(Ok(_)));
+    item
+  }
+}
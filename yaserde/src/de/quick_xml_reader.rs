@@ -0,0 +1,358 @@
+//! Event source backed by quick-xml.
+
+use super::text_limit::TextLimit;
+use crate::events::{OwnedName, ReadAttribute, ReadEvent};
+use quick_xml::events::attributes::Attributes;
+use quick_xml::events::{BytesStart, Event};
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::io::{BufReader, Read};
+use xml::namespace::{NamespaceStack, NS_NO_PREFIX};
+
+#[cfg(feature = "encoding_rs")]
+type Input<R> = super::decode::DecodeReader<R>;
+#[cfg(not(feature = "encoding_rs"))]
+type Input<R> = R;
+
+enum Source<'de, R: Read> {
+  Buffered(quick_xml::Reader<BufReader<TextLimit<Input<R>>>>, Vec<u8>),
+  /// Events borrow from the input string
+  Borrowed(quick_xml::Reader<&'de [u8]>, &'de str),
+}
+
+pub(crate) struct QuickXmlReader<'de, R: Read> {
+  source: Source<'de, R>,
+  namespaces: NamespaceStack,
+  /// Events to return, with whether they close a self-closed element
+  pending: VecDeque<(ReadEvent<'de>, bool)>,
+  self_closed: bool,
+  depth: usize,
+  has_root: bool,
+  error: Option<String>,
+  cdata_to_characters: bool,
+  max_text_size: Option<usize>,
+  /// Whether text merged from several events got over `max_text_size`
+  text_size_exceeded: bool,
+}
+
+impl<R: Read> QuickXmlReader<'_, R> {
+  pub(crate) fn new(reader: R, cdata_to_characters: bool, max_text_size: Option<usize>) -> Self {
+    #[cfg(feature = "encoding_rs")]
+    let reader = super::decode::DecodeReader::new(reader);
+    let reader = TextLimit::new(reader, max_text_size);
+    let reader = quick_xml::Reader::from_reader(BufReader::new(reader));
+
+    Self::new_from_source(
+      Source::Buffered(reader, Vec::new()),
+      cdata_to_characters,
+      max_text_size,
+    )
+  }
+}
+
+impl<'de> QuickXmlReader<'de, &'de [u8]> {
+  pub(crate) fn from_str(
+    input: &'de str,
+    cdata_to_characters: bool,
+    max_text_size: Option<usize>,
+  ) -> Self {
+    let reader = quick_xml::Reader::from_str(input);
+
+    Self::new_from_source(
+      Source::Borrowed(reader, input),
+      cdata_to_characters,
+      max_text_size,
+    )
+  }
+}
+
+impl<'de, R: Read> QuickXmlReader<'de, R> {
+  fn new_from_source(
+    source: Source<'de, R>,
+    cdata_to_characters: bool,
+    max_text_size: Option<usize>,
+  ) -> Self {
+    QuickXmlReader {
+      source,
+      namespaces: NamespaceStack::default(),
+      pending: VecDeque::new(),
+      self_closed: false,
+      depth: 0,
+      has_root: false,
+      error: None,
+      cdata_to_characters,
+      max_text_size,
+      text_size_exceeded: false,
+    }
+  }
+
+  pub(crate) fn next(&mut self) -> Result<ReadEvent<'de>, String> {
+    // Once an error is found, keep returning it like xml-rs
+    if let Some(error) = &self.error {
+      return Err(error.clone());
+    }
+
+    match self.read_next() {
+      Ok((event, self_closed)) => {
+        self.self_closed = self_closed;
+        Ok(event)
+      }
+      Err(error) => {
+        self.error = Some(error.clone());
+        Err(error)
+      }
+    }
+  }
+
+  /// Whether reading stopped on a text larger than `max_text_size`
+  pub(crate) fn text_size_exceeded(&self) -> bool {
+    match &self.source {
+      Source::Buffered(reader, _) => {
+        self.text_size_exceeded || reader.get_ref().get_ref().exceeded()
+      }
+      Source::Borrowed(..) => self.text_size_exceeded,
+    }
+  }
+
+  /// Whether the last event returned is the end of an element written as `<a/>`
+  pub(crate) fn self_closed(&self) -> bool {
+    self.self_closed
+  }
+
+  fn read_next(&mut self) -> Result<(ReadEvent<'de>, bool), String> {
+    if let Some(pending) = self.pending.pop_front() {
+      return Ok(pending);
+    }
+
+    // Coalesce text and CDATA sections read as text, as xml-rs does
+    let cdata_to_characters = self.cdata_to_characters;
+    let mut text: Option<Cow<'de, str>> = None;
+
+    loop {
+      let event = match &mut self.source {
+        Source::Buffered(reader, buf) => {
+          buf.clear();
+          let event = reader.read_event_into(buf).map_err(|e| e.to_string())?;
+          convert(&mut self.namespaces, event, cdata_to_characters, |_| None)?
+            .map(|(event, self_closed)| (event.into_owned(), self_closed))
+        }
+        Source::Borrowed(reader, input) => {
+          let event = reader.read_event().map_err(|e| e.to_string())?;
+          convert(&mut self.namespaces, event, cdata_to_characters, |bytes| {
+            subslice(input, bytes)
+          })?
+        }
+      };
+
+      let (event, self_closed) = match event {
+        Some((ReadEvent::Characters(content), _)) => {
+          let text = match &mut text {
+            Some(text) => {
+              text.to_mut().push_str(&content);
+              text
+            }
+            None => text.insert(content),
+          };
+          if matches!(self.max_text_size, Some(max) if text.len() > max) {
+            self.text_size_exceeded = true;
+            return Err("Text too large".to_string());
+          }
+          continue;
+        }
+        Some(event) => event,
+        None => continue,
+      };
+
+      match &event {
+        // `<a/>` leaves the depth as is
+        ReadEvent::StartElement { .. } if self_closed => self.has_root = true,
+        ReadEvent::StartElement { .. } => {
+          self.depth += 1;
+          self.has_root = true;
+        }
+        ReadEvent::EndElement { .. } => self.depth -= 1,
+        ReadEvent::EndDocument if !self.has_root => {
+          return Err("Unexpected end of stream: no root element found".to_string())
+        }
+        ReadEvent::EndDocument if self.depth > 0 => {
+          return Err("Unexpected end of stream".to_string())
+        }
+        _ => {}
+      }
+
+      // Report `<a/>` as a start and an end, as xml-rs does
+      let end = match &event {
+        ReadEvent::StartElement { name, .. } if self_closed => {
+          Some(ReadEvent::EndElement { name: name.clone() })
+        }
+        _ => None,
+      };
+
+      match text {
+        Some(text) if !text.is_empty() => {
+          self.pending.push_back((event, false));
+          self.pending.extend(end.map(|end| (end, true)));
+          return Ok((ReadEvent::Characters(text), false));
+        }
+        _ => {
+          self.pending.extend(end.map(|end| (end, true)));
+          return Ok((event, false));
+        }
+      }
+    }
+  }
+}
+
+/// Convert a quick-xml event, `None` when it is skipped. CDATA is returned
+/// as `Characters` with `cdata_to_characters`. The flag is set for `<a/>`.
+fn convert<'x>(
+  namespaces: &mut NamespaceStack,
+  event: Event<'x>,
+  cdata_to_characters: bool,
+  borrow: impl Fn(&[u8]) -> Option<&'x str>,
+) -> Result<Option<(ReadEvent<'x>, bool)>, String> {
+  let event = match event {
+    Event::Text(content) => ReadEvent::Characters(content.unescape().map_err(|e| e.to_string())?),
+    Event::CData(content) if cdata_to_characters => {
+      ReadEvent::Characters(content.decode().map_err(|e| e.to_string())?)
+    }
+    Event::CData(content) => ReadEvent::CData(content.decode().map_err(|e| e.to_string())?),
+    Event::DocType(_) => return Ok(None),
+    Event::Start(start) => start_element(namespaces, &start, borrow)?,
+    Event::Empty(start) => {
+      let event = start_element(namespaces, &start, borrow)?;
+      namespaces.try_pop();
+      return Ok(Some((event, true)));
+    }
+    Event::End(end) => {
+      let name = resolve(namespaces, &to_string(end.name().as_ref())?, true)?;
+      namespaces.try_pop();
+      ReadEvent::EndElement { name }
+    }
+    Event::Comment(content) => ReadEvent::Comment(Cow::Owned(to_string(&content)?)),
+    Event::Decl(declaration) => ReadEvent::StartDocument {
+      version: to_string(&declaration.version().map_err(|e| e.to_string())?)?,
+      encoding: match declaration.encoding() {
+        Some(encoding) => to_string(&encoding.map_err(|e| e.to_string())?)?,
+        None => "UTF-8".to_string(),
+      },
+      standalone: match declaration.standalone() {
+        Some(standalone) => Some(standalone.map_err(|e| e.to_string())?.as_ref() == b"yes"),
+        None => None,
+      },
+    },
+    Event::PI(instruction) => {
+      let data = to_string(instruction.content())?.trim().to_string();
+
+      ReadEvent::ProcessingInstruction {
+        name: to_string(instruction.target())?,
+        data: (!data.is_empty()).then_some(data),
+      }
+    }
+    Event::Eof => ReadEvent::EndDocument,
+  };
+
+  Ok(Some((event, false)))
+}
+
+fn start_element<'x>(
+  namespaces: &mut NamespaceStack,
+  start: &BytesStart<'x>,
+  borrow: impl Fn(&[u8]) -> Option<&'x str>,
+) -> Result<ReadEvent<'x>, String> {
+  namespaces.push_empty();
+
+  let name_len = start.name().as_ref().len();
+  let values = match borrow(&start[..]) {
+    Some(content) => attribute_values(Attributes::new(content, name_len), |value| value)?,
+    None => attribute_values(start.attributes(), |value| Cow::Owned(value.into_owned()))?,
+  };
+
+  let mut attributes = vec![];
+  for (key, value) in values {
+    match key.as_str() {
+      "xmlns" => namespaces.put(NS_NO_PREFIX, value.into_owned()),
+      _ if key.starts_with("xmlns:") => namespaces.put(&key["xmlns:".len()..], value.into_owned()),
+      _ => {
+        attributes.push((key, value));
+        continue;
+      }
+    };
+  }
+
+  let name = resolve(namespaces, &to_string(start.name().as_ref())?, true)?;
+  let attributes = attributes
+    .into_iter()
+    .map(|(key, value)| {
+      Ok(ReadAttribute {
+        name: resolve(namespaces, &key, false)?,
+        value,
+      })
+    })
+    .collect::<Result<_, String>>()?;
+
+  Ok(ReadEvent::StartElement {
+    name,
+    attributes,
+    namespace: namespaces.squash(),
+  })
+}
+
+fn attribute_values<'a, 'x>(
+  attributes: Attributes<'a>,
+  lift: impl Fn(Cow<'a, str>) -> Cow<'x, str>,
+) -> Result<Vec<(String, Cow<'x, str>)>, String> {
+  attributes
+    .map(|attribute| {
+      let attribute = attribute.map_err(|e| e.to_string())?;
+      let key = to_string(attribute.key.as_ref())?;
+      let value = attribute.unescape_value().map_err(|e| e.to_string())?;
+
+      Ok((key, lift(value)))
+    })
+    .collect()
+}
+
+/// Resolve a qualified name. Unprefixed attributes are never in the default namespace.
+fn resolve(
+  namespaces: &NamespaceStack,
+  qname: &str,
+  is_element: bool,
+) -> Result<OwnedName, String> {
+  let (prefix, local_name) = match qname.split_once(':') {
+    Some((prefix, local_name)) => (Some(prefix), local_name),
+    None => (None, qname),
+  };
+
+  let namespace = match prefix {
+    Some(prefix) => Some(
+      namespaces
+        .get(prefix)
+        .ok_or_else(|| format!("Element {} prefix is unbound", qname))?
+        .to_string(),
+    ),
+    None if is_element => namespaces
+      .get(NS_NO_PREFIX)
+      .filter(|uri| !uri.is_empty())
+      .map(String::from),
+    None => None,
+  };
+
+  Ok(OwnedName {
+    local_name: local_name.to_string(),
+    namespace,
+    prefix: prefix.map(String::from),
+  })
+}
+
+/// Get `bytes` back as a slice of `input`, if it points into it.
+fn subslice<'de>(input: &'de str, bytes: &[u8]) -> Option<&'de str> {
+  let offset = (bytes.as_ptr() as usize).checked_sub(input.as_ptr() as usize)?;
+  input.get(offset..offset + bytes.len())
+}
+
+fn to_string(bytes: &[u8]) -> Result<String, String> {
+  std::str::from_utf8(bytes)
+    .map(String::from)
+    .map_err(|e| e.to_string())
+}
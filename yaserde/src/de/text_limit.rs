@@ -0,0 +1,137 @@
+//! Limit the size of text while the document is read, so that a larger text
+//! node or CDATA section fails before the parser holds it in memory.
+
+use std::io::{Error, ErrorKind, Read, Result};
+
+/// Where the bytes read are in the document
+#[derive(Clone, Copy)]
+enum State {
+  Text,
+  /// Inside `&...;`, counted as one byte of text
+  Reference,
+  /// After `<`
+  Open,
+  /// After `<!`, with how many bytes of `--` or `[CDATA[` matched so far
+  Bang(usize),
+  /// Inside a tag or a DOCTYPE, with the open quote and whether it is in the
+  /// internal subset of the DOCTYPE
+  Tag(Option<u8>, bool),
+  /// Inside a comment, with how many `-` were just read
+  Comment(usize),
+  /// Inside a processing instruction, after a `?` or not
+  Instruction(bool),
+  /// Inside a CDATA section, with how many `]` were just read
+  CData(usize),
+}
+
+pub(crate) struct TextLimit<R: Read> {
+  inner: R,
+  max: Option<usize>,
+  state: State,
+  /// Bytes of the current text, comments and processing instructions aside
+  length: usize,
+  exceeded: bool,
+}
+
+impl<R: Read> TextLimit<R> {
+  pub(crate) fn new(inner: R, max: Option<usize>) -> Self {
+    TextLimit {
+      inner,
+      max,
+      state: State::Text,
+      length: 0,
+      exceeded: false,
+    }
+  }
+
+  /// Whether reading stopped on a text larger than the limit
+  pub(crate) fn exceeded(&self) -> bool {
+    self.exceeded
+  }
+
+  fn scan(&mut self, byte: u8) {
+    self.state = match (self.state, byte) {
+      (State::Text, b'<') => State::Open,
+      (State::Text, b'&') => {
+        self.length += 1;
+        State::Reference
+      }
+      // Line endings are normalized by the parsers
+      (State::Text, b'\r') => State::Text,
+      (State::Text, _) => {
+        self.length += 1;
+        State::Text
+      }
+      (State::Reference, b';') => State::Text,
+      (State::Reference, _) => State::Reference,
+      (State::Open, b'!') => State::Bang(0),
+      (State::Open, b'?') => State::Instruction(false),
+      (State::Open, _) => {
+        self.length = 0;
+        State::Tag(None, false)
+      }
+      (State::Bang(matched), _) if b"--".get(matched) == Some(&byte) => {
+        if matched == 1 {
+          State::Comment(0)
+        } else {
+          State::Bang(matched + 1)
+        }
+      }
+      (State::Bang(matched), _) if b"[CDATA[".get(matched) == Some(&byte) => {
+        if matched == 6 {
+          State::CData(0)
+        } else {
+          State::Bang(matched + 1)
+        }
+      }
+      (State::Bang(_), _) => {
+        self.length = 0;
+        State::Tag(None, byte == b'[')
+      }
+      (State::Tag(Some(quote), subset), _) if byte == quote => State::Tag(None, subset),
+      (State::Tag(Some(quote), subset), _) => State::Tag(Some(quote), subset),
+      (State::Tag(None, subset), b'"' | b'\'') => State::Tag(Some(byte), subset),
+      (State::Tag(None, _), b'[') => State::Tag(None, true),
+      (State::Tag(None, _), b']') => State::Tag(None, false),
+      (State::Tag(None, false), b'>') => State::Text,
+      (State::Tag(None, subset), _) => State::Tag(None, subset),
+      (State::Comment(dashes), b'>') if dashes >= 2 => State::Text,
+      (State::Comment(dashes), b'-') => State::Comment(dashes + 1),
+      (State::Comment(_), _) => State::Comment(0),
+      (State::Instruction(true), b'>') => State::Text,
+      (State::Instruction(_), _) => State::Instruction(byte == b'?'),
+      (State::CData(brackets), b'>') if brackets >= 2 => {
+        // The `]]` of the end were counted
+        self.length -= 2;
+        State::Text
+      }
+      (State::CData(brackets), _) => {
+        self.length += 1;
+        State::CData(if byte == b']' { brackets + 1 } else { 0 })
+      }
+    };
+  }
+}
+
+impl<R: Read> Read for TextLimit<R> {
+  fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+    let max = match self.max {
+      Some(max) => max,
+      None => return self.inner.read(buf),
+    };
+    if self.exceeded {
+      return Err(Error::new(ErrorKind::InvalidData, "text too large"));
+    }
+
+    let read = self.inner.read(buf)?;
+    for byte in &buf[..read] {
+      self.scan(*byte);
+      if self.length > max {
+        self.exceeded = true;
+        return Err(Error::new(ErrorKind::InvalidData, "text too large"));
+      }
+    }
+
+    Ok(read)
+  }
+}
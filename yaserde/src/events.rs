@@ -0,0 +1,241 @@
+//! XML events exchanged with the parser and the writer.
+//!
+//! Deserializers and generated code read [`ReadEvent`]s, whatever the [`Parser`](crate::de::Parser) is.
+//! Serializers write xml-rs events, re-exported as [`WriteEvent`]: there is no
+//! other writer.
+//! Text and attribute values may borrow from the input document for the `'de` lifetime.
+
+use std::borrow::Cow;
+use std::fmt;
+
+pub use xml::attribute::{Attribute, OwnedAttribute};
+pub use xml::common::XmlVersion;
+pub use xml::name::{Name, OwnedName};
+pub use xml::namespace::Namespace;
+pub use xml::writer::XmlEvent as WriteEvent;
+
+/// An attribute read from an XML document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadAttribute<'de> {
+  pub name: OwnedName,
+  pub value: Cow<'de, str>,
+}
+
+impl<'de> ReadAttribute<'de> {
+  pub fn borrow(&self) -> Attribute<'_> {
+    Attribute::new(self.name.borrow(), &self.value)
+  }
+
+  pub fn into_owned(self) -> ReadAttribute<'static> {
+    ReadAttribute {
+      name: self.name,
+      value: Cow::Owned(self.value.into_owned()),
+    }
+  }
+}
+
+impl From<OwnedAttribute> for ReadAttribute<'_> {
+  fn from(attribute: OwnedAttribute) -> Self {
+    ReadAttribute {
+      name: attribute.name,
+      value: Cow::Owned(attribute.value),
+    }
+  }
+}
+
+/// The XML declaration starting a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Declaration {
+  pub version: XmlVersion,
+  /// Left out of the declaration when `None`
+  pub encoding: Option<String>,
+  pub standalone: Option<bool>,
+}
+
+impl Default for Declaration {
+  fn default() -> Self {
+    Declaration {
+      version: XmlVersion::Version10,
+      encoding: Some("utf-8".to_string()),
+      standalone: None,
+    }
+  }
+}
+
+impl fmt::Display for Declaration {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, r#"<?xml version="{}""#, self.version)?;
+    if let Some(encoding) = &self.encoding {
+      write!(f, r#" encoding="{}""#, encoding)?;
+    }
+    if let Some(standalone) = self.standalone {
+      write!(
+        f,
+        r#" standalone="{}""#,
+        if standalone { "yes" } else { "no" }
+      )?;
+    }
+    write!(f, "?>")
+  }
+}
+
+/// An item written between the declaration and the root element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Prolog {
+  ProcessingInstruction(crate::ProcessingInstruction),
+  /// Content of `<!DOCTYPE ...>`, like `html` or `note SYSTEM "note.dtd"`
+  DocType(String),
+}
+
+impl fmt::Display for Prolog {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Prolog::ProcessingInstruction(instruction) => instruction.fmt(f),
+      Prolog::DocType(doctype) => write!(f, "<!DOCTYPE {}>", doctype),
+    }
+  }
+}
+
+/// An event read from an XML document.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReadEvent<'de> {
+  StartDocument {
+    version: String,
+    encoding: String,
+    standalone: Option<bool>,
+  },
+  EndDocument,
+  ProcessingInstruction {
+    name: String,
+    data: Option<String>,
+  },
+  StartElement {
+    name: OwnedName,
+    attributes: Vec<ReadAttribute<'de>>,
+    namespace: Namespace,
+  },
+  EndElement {
+    name: OwnedName,
+  },
+  CData(Cow<'de, str>),
+  Comment(Cow<'de, str>),
+  Characters(Cow<'de, str>),
+  Whitespace(Cow<'de, str>),
+}
+
+impl<'de> ReadEvent<'de> {
+  /// Copy borrowed text so that the event no longer refers to the input.
+  pub fn into_owned(self) -> ReadEvent<'static> {
+    let owned = |data: Cow<'de, str>| Cow::Owned(data.into_owned());
+
+    match self {
+      ReadEvent::StartDocument {
+        version,
+        encoding,
+        standalone,
+      } => ReadEvent::StartDocument {
+        version,
+        encoding,
+        standalone,
+      },
+      ReadEvent::EndDocument => ReadEvent::EndDocument,
+      ReadEvent::ProcessingInstruction { name, data } => {
+        ReadEvent::ProcessingInstruction { name, data }
+      }
+      ReadEvent::StartElement {
+        name,
+        attributes,
+        namespace,
+      } => ReadEvent::StartElement {
+        name,
+        attributes: attributes
+          .into_iter()
+          .map(ReadAttribute::into_owned)
+          .collect(),
+        namespace,
+      },
+      ReadEvent::EndElement { name } => ReadEvent::EndElement { name },
+      ReadEvent::CData(data) => ReadEvent::CData(owned(data)),
+      ReadEvent::Comment(data) => ReadEvent::Comment(owned(data)),
+      ReadEvent::Characters(data) => ReadEvent::Characters(owned(data)),
+      ReadEvent::Whitespace(data) => ReadEvent::Whitespace(owned(data)),
+    }
+  }
+
+  /// Get the matching event to write it back, `None` for `EndDocument`.
+  pub fn as_writer_event(&self) -> Option<WriteEvent<'_>> {
+    match self {
+      ReadEvent::StartDocument {
+        version,
+        encoding,
+        standalone,
+      } => Some(WriteEvent::StartDocument {
+        version: if version == "1.1" {
+          XmlVersion::Version11
+        } else {
+          XmlVersion::Version10
+        },
+        encoding: Some(encoding),
+        standalone: *standalone,
+      }),
+      ReadEvent::EndDocument => None,
+      ReadEvent::ProcessingInstruction { name, data } => Some(WriteEvent::ProcessingInstruction {
+        name,
+        data: data.as_deref(),
+      }),
+      ReadEvent::StartElement {
+        name,
+        attributes,
+        namespace,
+      } => Some(WriteEvent::StartElement {
+        name: name.borrow(),
+        attributes: attributes.iter().map(ReadAttribute::borrow).collect(),
+        namespace: Cow::Borrowed(namespace),
+      }),
+      ReadEvent::EndElement { name } => Some(WriteEvent::EndElement {
+        name: Some(name.borrow()),
+      }),
+      ReadEvent::CData(data) => Some(WriteEvent::CData(data)),
+      ReadEvent::Comment(data) => Some(WriteEvent::Comment(data)),
+      ReadEvent::Characters(data) | ReadEvent::Whitespace(data) => {
+        Some(WriteEvent::Characters(data))
+      }
+    }
+  }
+}
+
+impl From<xml::reader::XmlEvent> for ReadEvent<'_> {
+  fn from(event: xml::reader::XmlEvent) -> Self {
+    use xml::reader::XmlEvent;
+
+    match event {
+      XmlEvent::StartDocument {
+        version,
+        encoding,
+        standalone,
+      } => ReadEvent::StartDocument {
+        version: version.to_string(),
+        encoding,
+        standalone,
+      },
+      XmlEvent::EndDocument => ReadEvent::EndDocument,
+      XmlEvent::ProcessingInstruction { name, data } => {
+        ReadEvent::ProcessingInstruction { name, data }
+      }
+      XmlEvent::StartElement {
+        name,
+        attributes,
+        namespace,
+      } => ReadEvent::StartElement {
+        name,
+        attributes: attributes.into_iter().map(ReadAttribute::from).collect(),
+        namespace,
+      },
+      XmlEvent::EndElement { name } => ReadEvent::EndElement { name },
+      XmlEvent::CData(data) => ReadEvent::CData(Cow::Owned(data)),
+      XmlEvent::Comment(data) => ReadEvent::Comment(Cow::Owned(data)),
+      XmlEvent::Characters(data) => ReadEvent::Characters(Cow::Owned(data)),
+      XmlEvent::Whitespace(data) => ReadEvent::Whitespace(Cow::Owned(data)),
+    }
+  }
+}
@@ -88,24 +88,37 @@ extern crate yaserde_derive;
 #[doc(hidden)]
 pub use yaserde_derive::*;
 
+use std::borrow::Cow;
 use std::io::{Read, Write};
 use xml::writer::XmlEvent;
 
 pub mod de;
+pub mod events;
 pub mod primitives;
 pub mod ser;
 mod named_list;
 mod maybe_string;
+mod nullable;
+mod processing_instruction;
 mod raw_xml;
 pub use maybe_string::MaybeString;
 pub use named_list::NamedList;
+pub use nullable::Nullable;
+pub use processing_instruction::ProcessingInstruction;
 pub use raw_xml::RawXml;
 
 /// A **data structure** that can be deserialized from any data format supported by YaSerDe.
-pub trait YaDeserialize: Sized {
-  fn deserialize<R: Read>(reader: &mut de::Deserializer<R>) -> Result<Self, String>;
+///
+/// The `'de` lifetime is the one of the input, which `&'de str` and `Cow<'de, str>` fields can borrow from.
+pub trait YaDeserialize<'de>: Sized {
+  fn deserialize<R: Read>(reader: &mut de::Deserializer<'de, R>) -> Result<Self, String>;
 }
 
+/// A **data structure** that can be deserialized without borrowing from the input.
+pub trait YaDeserializeOwned: for<'de> YaDeserialize<'de> {}
+
+impl<T> YaDeserializeOwned for T where T: for<'de> YaDeserialize<'de> {}
+
 /// A **data structure** that can be serialized into any data format supported by YaSerDe.
 pub trait YaSerialize: Sized {
   fn serialize<W: Write>(&self, writer: &mut ser::Serializer<W>) -> Result<(), String>;
@@ -175,6 +188,19 @@ pub trait Visitor<'de>: Sized {
   fn visit_str(self, v: &str) -> Result<Self::Value, String> {
     Err(format!("Unexpected str {:?}", v))
   }
+
+  /// Visit a string which lives as long as the input.
+  fn visit_borrowed_str(self, v: &'de str) -> Result<Self::Value, String> {
+    self.visit_str(v)
+  }
+
+  /// Visit text read from the document, borrowing it when possible.
+  fn visit_cow_str(self, v: &Cow<'de, str>) -> Result<Self::Value, String> {
+    match v {
+      Cow::Borrowed(v) => self.visit_borrowed_str(v),
+      Cow::Owned(v) => self.visit_str(v),
+    }
+  }
 }
 
 macro_rules! serialize_type {
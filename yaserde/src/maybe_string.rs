@@ -1,11 +1,14 @@
+use crate::events::ReadEvent;
+use crate::ser::EmptyElement;
 use crate::{ser, YaDeserialize, YaSerialize};
-use xml::reader::XmlEvent as ReadEvent;
 use xml::writer::XmlEvent as WriteEvent;
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct MaybeString {
   pub field_name: String,
   pub content: Option<String>,
+  /// How the element is written without content, remembered from the parsed document
+  pub empty_element: Option<EmptyElement>,
 }
 
 impl MaybeString {
@@ -13,6 +16,7 @@ impl MaybeString {
     MaybeString {
       field_name: field_name.to_string(),
       content: Some(format!("{}", b as i8)),
+      empty_element: None,
     }
   }
 
@@ -26,6 +30,7 @@ impl From<Option<String>> for MaybeString {
     Self {
       field_name: String::default(),
       content: value,
+      empty_element: None,
     }
   }
 }
@@ -35,6 +40,7 @@ impl From<String> for MaybeString {
     Self {
       field_name: String::default(),
       content: Some(value),
+      empty_element: None,
     }
   }
 }
@@ -44,19 +50,20 @@ impl From<&str> for MaybeString {
     Self {
       field_name: String::default(),
       content: Some(String::from(value)),
+      empty_element: None,
     }
   }
 }
 
-impl YaDeserialize for MaybeString {
+impl<'de> YaDeserialize<'de> for MaybeString {
   fn deserialize<R: std::io::Read>(
-    reader: &mut crate::de::Deserializer<R>,
+    reader: &mut crate::de::Deserializer<'de, R>,
   ) -> Result<Self, String> {
     let field_name = match reader.peek()? {
       ReadEvent::StartElement {
         name, attributes, ..
       } => {
-        if attributes.len() > 0 {
+        if !attributes.is_empty() {
           return Err(String::from(
             "Attributes not currently supported by MaybeString",
           ));
@@ -68,8 +75,16 @@ impl YaDeserialize for MaybeString {
     };
     reader.next_event()?;
 
-    let content = match reader.peek()? {
-      ReadEvent::Characters(content) => Some(content.clone()),
+    let empty_element = if reader.peek_self_closed()? {
+      EmptyElement::SelfClosing
+    } else {
+      EmptyElement::Explicit
+    };
+
+    let (content, empty_element) = match reader.peek()? {
+      ReadEvent::Characters(content) | ReadEvent::CData(content) => {
+        (Some(content.to_string()), None)
+      }
       ReadEvent::EndElement { name } => {
         if name.local_name != field_name {
           return Err(format!(
@@ -77,7 +92,7 @@ impl YaDeserialize for MaybeString {
             name.local_name
           ));
         }
-        None
+        (None, Some(empty_element))
       }
       _ => return Err(String::from("Unsupporte ReadEvent type")),
     };
@@ -85,32 +100,40 @@ impl YaDeserialize for MaybeString {
     Ok(Self {
       field_name,
       content,
+      empty_element,
     })
   }
 }
 
 impl YaSerialize for MaybeString {
   fn serialize<W: std::io::Write>(&self, writer: &mut ser::Serializer<W>) -> Result<(), String> {
+    let field_empty_element = writer.get_empty_element();
+    if self.empty_element.is_some() {
+      writer.set_empty_element(self.empty_element);
+    }
+
     if let Some(field_name) = writer.get_start_event_name() {
       let start_element_event = WriteEvent::start_element(field_name.as_str());
-      writer.write(start_element_event).expect("Writer failed");
+      writer
+        .write(start_element_event)
+        .map_err(|e| e.to_string())?;
     } else {
       let start_element_event = WriteEvent::start_element(self.field_name.as_str());
-      writer.write(start_element_event).expect("Writer failed");
+      writer
+        .write(start_element_event)
+        .map_err(|e| e.to_string())?;
     };
 
-    match &self.content {
-      Some(content) => {
-        writer
-          .write(WriteEvent::characters(content))
-          .expect("Writer failed");
-      }
-      None => {}
+    if let Some(content) = &self.content {
+      writer
+        .write(WriteEvent::characters(content))
+        .map_err(|e| e.to_string())?;
     };
 
     writer
       .write(WriteEvent::end_element())
-      .expect("Writer failed");
+      .map_err(|e| e.to_string())?;
+    writer.set_empty_element(field_empty_element);
     Ok(())
   }
 
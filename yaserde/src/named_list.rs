@@ -3,25 +3,26 @@ use log::debug;
 use crate::de::Deserializer;
 
 #[derive(Default, PartialEq, Debug)]
-pub struct NamedList<T: crate::YaDeserialize + crate::YaSerialize + std::fmt::Debug> {
+pub struct NamedList<T: crate::YaSerialize + std::fmt::Debug> {
   pub elements: Vec<(String, T)>,
 }
 
-impl<T> crate::YaDeserialize for NamedList<T>
+impl<'de, T> crate::YaDeserialize<'de> for NamedList<T>
 where
-  T: crate::YaDeserialize + crate::YaSerialize + std::fmt::Debug,
+  T: crate::YaDeserialize<'de> + crate::YaSerialize + std::fmt::Debug,
 {
-  fn deserialize<R: std::io::Read>(reader: &mut Deserializer<R>) -> Result<Self, String> {
+  fn deserialize<R: std::io::Read>(reader: &mut Deserializer<'de, R>) -> Result<Self, String> {
     log::trace!("NamedList peek {:?}", reader.peek()?);
-    if let xml::reader::XmlEvent::StartElement {
+    let depth = reader.depth();
+    if let crate::events::ReadEvent::StartElement {
       name, attributes, ..
-    } = reader.peek()?.to_owned()
+    } = reader.peek()?
     {
       log::trace!(
         "StartElement with name {} attributes {:?} depth {}",
         name,
         attributes,
-        reader.depth()
+        depth
       );
       // Get inside the List.
       // We skip the opening StartElement to get to the list itself
@@ -35,36 +36,34 @@ where
     let mut elements: Vec<(String, T)> = Vec::new();
     let start_depth = reader.depth();
     loop {
-      let current_event = reader.peek()?.to_owned();
+      let depth = reader.depth();
+      let current_event = reader.peek()?;
       log::trace!("NamedList loop iterating on event {:?}", current_event);
       match current_event {
-        xml::reader::XmlEvent::StartDocument { .. } => {
-          unimplemented!("NamedList deserializer got StartDocument");
+        crate::events::ReadEvent::StartDocument { .. }
+        | crate::events::ReadEvent::ProcessingInstruction { .. }
+        | crate::events::ReadEvent::Comment(_)
+        | crate::events::ReadEvent::Whitespace(_) => {
+          reader.next_event()?;
         }
-        xml::reader::XmlEvent::EndDocument => {
-          unimplemented!("NamedList deserializer got EndDocument");
+        crate::events::ReadEvent::EndDocument => {
+          return Err(String::from("Unexpected end of document in NamedList"));
         }
-        xml::reader::XmlEvent::ProcessingInstruction { .. } => {
-          unimplemented!("NamedList deserializer got ProcessingInstruction")
-        }
-        xml::reader::XmlEvent::StartElement {
+        crate::events::ReadEvent::StartElement {
           name, namespace, ..
         } => {
           log::trace!(
             "NamedList deserializer got StartElement name {:?} namespace {:?}",
-            name, namespace,
+            name,
+            namespace,
           );
+          let name = name.to_string();
           let child = T::deserialize(reader)?;
           log::trace!("NamedList deserialize inserting child {:?}", child);
-          elements.push((name.to_string(), child));
+          elements.push((name, child));
         }
-        xml::reader::XmlEvent::EndElement { name } => {
-          log::trace!("NamedList deserializer got EndElement {name}");
-          log::trace!(
-            "Next_event() Peek : {:?}, depth : {}",
-            reader.peek()?.to_owned(),
-            reader.depth()
-          );
+        crate::events::ReadEvent::EndElement { name } => {
+          log::trace!("NamedList deserializer got EndElement {name}, depth : {depth}");
           if reader.depth() > start_depth {
             log::trace!(
               "Current depth {} greater than start depth {}, consuming event",
@@ -82,13 +81,12 @@ where
             break;
           }
         }
-        xml::reader::XmlEvent::CData(_) => unimplemented!("NamedList deserializer got CData"),
-        xml::reader::XmlEvent::Comment(_) => unimplemented!("NamedList deserializer got Comment"),
-        xml::reader::XmlEvent::Characters(_) => {
-          unimplemented!("NamedList deserializer got Characters")
-        }
-        xml::reader::XmlEvent::Whitespace(_) => {
-          unimplemented!("NamedList deserializer got Whitespace")
+        crate::events::ReadEvent::Characters(text) | crate::events::ReadEvent::CData(text) => {
+          // Untrimmed whitespace between the elements
+          if !text.trim().is_empty() {
+            return Err(format!("Unexpected text in NamedList: {}", text));
+          }
+          reader.next_event()?;
         }
       }
     }
@@ -101,9 +99,7 @@ where
   }
 }
 
-impl<T: crate::YaDeserialize + crate::YaSerialize + std::fmt::Debug> crate::YaSerialize
-  for NamedList<T>
-{
+impl<T: crate::YaSerialize + std::fmt::Debug> crate::YaSerialize for NamedList<T> {
   fn serialize<W: std::io::Write>(
     &self,
     writer: &mut crate::ser::Serializer<W>,
@@ -147,7 +143,7 @@ impl<T: crate::YaDeserialize + crate::YaSerialize + std::fmt::Debug> crate::YaSe
     log::trace!("NamedList writing FINAL end element");
     writer
       .write(element)
-      .map_err(|_e| format!("NamedList FINAL End element write failed"))?;
+      .map_err(|_e| "NamedList FINAL End element write failed".to_string())?;
 
     Ok(())
   }
@@ -175,8 +171,9 @@ mod test {
 
   #[test]
   fn deserialize_hashmap() {
-    let mut expected_elements: Vec<(String, RawXml)> = Vec::new();
-    expected_elements.push((String::from("struct1"), RawXml::default()));
+    // The items are the children of the root element
+    let expected_elements: Vec<(String, RawXml)> =
+      vec![(String::from("foo"), RawXml(String::from("<foo>foo</foo>")))];
 
     let deserialized: NamedList<RawXml> =
       crate::de::from_str("<struct1><foo>foo</foo></struct1>").unwrap();
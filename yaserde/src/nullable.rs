@@ -0,0 +1,102 @@
+use crate::events::ReadEvent;
+use crate::{de, ser, YaDeserialize, YaSerialize};
+use xml::writer::XmlEvent as WriteEvent;
+
+/// An element that can be missing, empty (`<x />`) or hold a value
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Nullable<T> {
+  #[default]
+  Absent,
+  Empty,
+  Value(T),
+}
+
+impl<T> Nullable<T> {
+  pub fn is_absent(&self) -> bool {
+    matches!(self, Nullable::Absent)
+  }
+
+  pub fn is_empty(&self) -> bool {
+    matches!(self, Nullable::Empty)
+  }
+
+  pub fn as_ref(&self) -> Nullable<&T> {
+    match self {
+      Nullable::Absent => Nullable::Absent,
+      Nullable::Empty => Nullable::Empty,
+      Nullable::Value(value) => Nullable::Value(value),
+    }
+  }
+
+  /// Get the value, dropping the difference between an absent and an empty element
+  pub fn value(self) -> Option<T> {
+    match self {
+      Nullable::Value(value) => Some(value),
+      _ => None,
+    }
+  }
+}
+
+impl<T> From<T> for Nullable<T> {
+  fn from(value: T) -> Self {
+    Nullable::Value(value)
+  }
+}
+
+impl<'de, T: YaDeserialize<'de>> YaDeserialize<'de> for Nullable<T> {
+  fn deserialize<R: std::io::Read>(reader: &mut de::Deserializer<'de, R>) -> Result<Self, String> {
+    if !matches!(reader.peek()?, ReadEvent::StartElement { .. }) {
+      return Err("Nullable should start deserializing with StartElement".to_string());
+    }
+
+    if let ReadEvent::EndElement { .. } = reader.peek_nth(1)? {
+      // Leave the EndElement to the caller, as for any other element
+      reader.next_event()?;
+      return Ok(Nullable::Empty);
+    }
+
+    T::deserialize(reader).map(Nullable::Value)
+  }
+}
+
+impl<T: YaSerialize> YaSerialize for Nullable<T> {
+  fn serialize<W: std::io::Write>(&self, writer: &mut ser::Serializer<W>) -> Result<(), String> {
+    match self {
+      Nullable::Absent => Ok(()),
+      Nullable::Empty => {
+        if writer.skip_start_end() {
+          return Ok(());
+        }
+
+        let name = writer
+          .get_start_event_name()
+          .ok_or_else(|| "Nullable requires an element name".to_string())?;
+
+        writer
+          .write(WriteEvent::start_element(name.as_str()))
+          .map_err(|e| e.to_string())?;
+        writer
+          .write(WriteEvent::end_element())
+          .map_err(|e| e.to_string())
+      }
+      Nullable::Value(value) => value.serialize(writer),
+    }
+  }
+
+  fn serialize_attributes(
+    &self,
+    attributes: Vec<xml::attribute::OwnedAttribute>,
+    namespace: xml::namespace::Namespace,
+  ) -> Result<
+    (
+      Vec<xml::attribute::OwnedAttribute>,
+      xml::namespace::Namespace,
+    ),
+    String,
+  > {
+    match self {
+      Nullable::Value(value) => value.serialize_attributes(attributes, namespace),
+      _ => Ok((attributes, namespace)),
+    }
+  }
+}
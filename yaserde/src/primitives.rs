@@ -34,18 +34,45 @@ pub fn serialize_primitives<S, W: Write>(
 }
 
 pub fn deserialize_primitives<S, R: Read>(
-  reader: &mut de::Deserializer<R>,
+  reader: &mut de::Deserializer<'_, R>,
   deserialize_function: impl FnOnce(&str) -> Result<S, String>,
 ) -> Result<S, String> {
-  if let Ok(xml::reader::XmlEvent::StartElement { .. }) = reader.peek() {
+  if let Ok(crate::events::ReadEvent::StartElement { .. }) = reader.peek() {
     reader.next_event()?;
   } else {
     return Err("Start element not found".to_string());
   }
 
-  if let Ok(xml::reader::XmlEvent::Characters(ref text)) = reader.peek() {
+  if let Ok(
+    crate::events::ReadEvent::Characters(ref text) | crate::events::ReadEvent::CData(ref text),
+  ) = reader.peek()
+  {
     deserialize_function(text)
   } else {
     deserialize_function("")
   }
 }
+
+/// Format a boolean with the given representations of `true` and `false`
+pub fn serialize_bool(value: &bool, true_value: &str, false_value: &str) -> String {
+  if *value {
+    true_value.to_string()
+  } else {
+    false_value.to_string()
+  }
+}
+
+/// Parse a boolean from the given representations of `true` and `false`.
+/// `true`/`false` and `1`/`0` are always accepted.
+pub fn deserialize_bool(value: &str, true_value: &str, false_value: &str) -> Result<bool, String> {
+  match value {
+    "true" | "1" => Ok(true),
+    "false" | "0" => Ok(false),
+    value if value == true_value => Ok(true),
+    value if value == false_value => Ok(false),
+    value => Err(format!(
+      "invalid boolean {:?}, expected {:?} or {:?}",
+      value, true_value, false_value
+    )),
+  }
+}
@@ -0,0 +1,72 @@
+use crate::events::ReadEvent;
+use crate::{de, ser, YaDeserialize, YaSerialize};
+use std::fmt;
+use xml::writer::XmlEvent as WriteEvent;
+
+/// A processing instruction, like `<?xml-stylesheet href="style.css"?>`.
+///
+/// The first field of a struct of this type, or a sequence of it, collects
+/// the processing instructions directly inside the element of the struct.
+/// They are written back at the position of the field.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ProcessingInstruction {
+  pub name: String,
+  pub data: Option<String>,
+}
+
+impl ProcessingInstruction {
+  pub fn new(name: &str, data: Option<&str>) -> Self {
+    ProcessingInstruction {
+      name: name.to_string(),
+      data: data.map(String::from),
+    }
+  }
+}
+
+impl fmt::Display for ProcessingInstruction {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match &self.data {
+      Some(data) => write!(f, "<?{} {}?>", self.name, data),
+      None => write!(f, "<?{}?>", self.name),
+    }
+  }
+}
+
+impl<'de> YaDeserialize<'de> for ProcessingInstruction {
+  fn deserialize<R: std::io::Read>(reader: &mut de::Deserializer<'de, R>) -> Result<Self, String> {
+    match reader.next_event()? {
+      ReadEvent::ProcessingInstruction { name, data } => Ok(ProcessingInstruction { name, data }),
+      event => Err(format!(
+        "expected a processing instruction, found {:?}",
+        event
+      )),
+    }
+  }
+}
+
+impl YaSerialize for ProcessingInstruction {
+  fn serialize<W: std::io::Write>(&self, writer: &mut ser::Serializer<W>) -> Result<(), String> {
+    // Written as is, whatever the field is named
+    writer.set_start_event_name(None);
+    writer
+      .write(WriteEvent::processing_instruction(
+        &self.name,
+        self.data.as_deref(),
+      ))
+      .map_err(|e| e.to_string())
+  }
+
+  fn serialize_attributes(
+    &self,
+    attributes: Vec<xml::attribute::OwnedAttribute>,
+    namespace: xml::namespace::Namespace,
+  ) -> Result<
+    (
+      Vec<xml::attribute::OwnedAttribute>,
+      xml::namespace::Namespace,
+    ),
+    String,
+  > {
+    Ok((attributes, namespace))
+  }
+}
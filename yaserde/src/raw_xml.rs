@@ -1,24 +1,25 @@
+use crate::events::ReadEvent;
+use crate::ser::EmptyElement;
 use crate::{ser, YaDeserialize as YaDeserializeTrait, YaSerialize as YaSerializeTrait};
-use xml::reader::XmlEvent as ReadEvent;
 
 #[derive(Debug, PartialEq, Default)]
 pub struct RawXml(pub String);
 
-impl YaDeserializeTrait for RawXml {
+impl<'de> YaDeserializeTrait<'de> for RawXml {
   fn deserialize<R: std::io::Read>(
-    reader: &mut crate::de::Deserializer<R>,
+    reader: &mut crate::de::Deserializer<'de, R>,
   ) -> Result<Self, String> {
     let mut buffer = String::new();
     let mut depth = 0;
 
-    let own_name = match reader.peek()? {
-      ReadEvent::StartElement { name, .. } => name.local_name.clone(),
+    let own_name = match reader.peek_name()? {
+      Some(name) => name.local_name.clone(),
       _ => return Err("RawXml Should start deserializing with StartElement".to_string()),
     };
     log::trace!("RawXml deserialize from root element name : {own_name}");
     loop {
-      let current_event = reader.peek()?.to_owned();
-      match current_event.clone() {
+      let self_closed = reader.peek_self_closed()?;
+      match reader.peek()? {
         ReadEvent::StartElement {
           name, attributes, ..
         } => {
@@ -34,10 +35,17 @@ impl YaDeserializeTrait for RawXml {
         ReadEvent::EndElement { name } => {
           log::trace!("EndElement {name} depth {depth}");
           depth -= 1;
-          buffer.push_str(&format!("</{}>", name));
+          if self_closed {
+            // Keep `<a/>` as it was written, right after its start tag
+            buffer.pop();
+            buffer.push_str("/>");
+          } else {
+            buffer.push_str(&format!("</{}>", name));
+          }
           log::trace!(
             "Checking if name.local_name {} matches own_name {} at depth {depth}",
-            &name.local_name, &own_name
+            &name.local_name,
+            &own_name
           );
           if name.local_name == own_name && depth == 0 {
             log::trace!("Found next EndElement is closing my struct, breaking out of loop");
@@ -48,32 +56,35 @@ impl YaDeserializeTrait for RawXml {
         }
         ReadEvent::Characters(content) => {
           log::trace!("Characters {content} depth {depth}");
-          buffer.push_str(&content);
+          buffer.push_str(content);
+          let _event = reader.next_event()?;
+        }
+        ReadEvent::StartDocument { .. } => {
           let _event = reader.next_event()?;
         }
-        ReadEvent::StartDocument {
-          version,
-          encoding,
-          standalone,
-        } => todo!(
-          "StartDocument {:?} {:?} {:?}",
-          version,
-          encoding,
-          standalone
-        ),
-        ReadEvent::EndDocument => todo!(),
+        ReadEvent::EndDocument => {
+          return Err(format!("Unexpected end of document in RawXml {}", own_name))
+        }
         ReadEvent::ProcessingInstruction { name, data } => {
-          todo!("ProcessingInstruction {:?}, {:?}", name, data)
+          match data {
+            Some(data) => buffer.push_str(&format!("<?{} {}?>", name, data)),
+            None => buffer.push_str(&format!("<?{}?>", name)),
+          }
+          let _event = reader.next_event()?;
+        }
+        ReadEvent::CData(cdata) => {
+          buffer.push_str(&format!("<![CDATA[{}]]>", cdata));
+          let _event = reader.next_event()?;
+        }
+        ReadEvent::Comment(comment) => {
+          buffer.push_str(&format!("<!--{}-->", comment));
+          let _event = reader.next_event()?;
+        }
+        ReadEvent::Whitespace(whitespace) => {
+          buffer.push_str(whitespace);
+          let _event = reader.next_event()?;
         }
-        ReadEvent::CData(cdata) => todo!("CData, {:?}", cdata),
-        ReadEvent::Comment(comment) => todo!("Comment, {:?}", comment),
-        ReadEvent::Whitespace(whitespace) => todo!("Whitespace, {:?}", whitespace),
       }
-      let next = reader.peek()?;
-      log::trace!(
-        "Processing done on \ncurrent_event : {:?} \nnext : {:?}",
-        &current_event, &next
-      );
     }
 
     log::trace!("buffered events {buffer}");
@@ -89,15 +100,24 @@ impl YaSerializeTrait for RawXml {
     let content = self.0.clone();
     let content = xml::EventReader::from_str(content.as_str());
     let mut reader = crate::de::Deserializer::new(content);
+    let field_empty_element = writer.get_empty_element();
     loop {
       let e = reader.next_event()?;
       if let ReadEvent::EndDocument = e {
         break;
       }
-      writer
-        .write(e.as_writer_event().unwrap())
-        .expect("Writer should write write event");
+      if let ReadEvent::StartElement { .. } = e {
+        writer.set_empty_element(Some(if reader.peek_self_closed()? {
+          EmptyElement::SelfClosing
+        } else {
+          EmptyElement::Explicit
+        }));
+      }
+      if let Some(event) = e.as_writer_event() {
+        writer.write(event).map_err(|e| e.to_string())?;
+      }
     }
+    writer.set_empty_element(field_empty_element);
     Ok(())
   }
 
@@ -112,6 +132,6 @@ impl YaSerializeTrait for RawXml {
     ),
     String,
   > {
-    todo!()
+    Err("RawXml does not support attributes".to_string())
   }
 }
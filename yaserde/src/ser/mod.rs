@@ -1,26 +1,89 @@
 //! Generic data structure serialization framework.
 //!
 
+use crate::events::{Declaration, Prolog};
 use crate::YaSerialize;
-use std::io::{Cursor, Write};
-use std::str;
+use output::Output;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::io::Write;
+use xml::attribute::{Attribute, OwnedAttribute};
+use xml::name::{Name, OwnedName};
+use xml::namespace::{Namespace, NamespaceStack, NS_NO_PREFIX};
 use xml::writer::XmlEvent;
 use xml::{EmitterConfig, EventWriter};
 
+mod output;
+
 /// Serialize XML into a plain String with no formatting (EmitterConfig).
 pub fn to_string<T: YaSerialize>(model: &T) -> Result<String, String> {
-  let buf = Cursor::new(Vec::new());
-  let cursor = serialize_with_writer(model, buf, &Config::default())?;
-  let data = str::from_utf8(cursor.get_ref()).expect("Found invalid UTF-8");
-  Ok(data.into())
+  to_string_with_config(model, &Config::default())
 }
 
 /// Serialize XML into a plain String with control on formatting (via EmitterConfig parameters)
 pub fn to_string_with_config<T: YaSerialize>(model: &T, config: &Config) -> Result<String, String> {
-  let buf = Cursor::new(Vec::new());
-  let cursor = serialize_with_writer(model, buf, config)?;
-  let data = str::from_utf8(cursor.get_ref()).expect("Found invalid UTF-8");
-  Ok(data.into())
+  #[cfg(feature = "encoding_rs")]
+  if config.encoding != encoding_rs::UTF_8 {
+    return Err(format!(
+      "unable to write {} in a String, use to_writer_with_config",
+      config.encoding.name()
+    ));
+  }
+
+  let buf = serialize_with_writer(model, Vec::new(), config)?;
+  into_utf8_string(buf)
+}
+
+/// Serialize XML straight into a writer, without buffering the whole document.
+pub fn to_writer<W: Write, T: YaSerialize>(model: &T, writer: W) -> Result<(), String> {
+  to_writer_with_config(model, writer, &Config::default())
+}
+
+/// Serialize XML straight into a writer with control on formatting
+pub fn to_writer_with_config<W: Write, T: YaSerialize>(
+  model: &T,
+  writer: W,
+  config: &Config,
+) -> Result<(), String> {
+  serialize_with_writer(model, writer, config).map(|_writer| ())
+}
+
+/// Serialize XML into an async writer without blocking the executor.
+///
+/// The model is moved to the blocking thread pool and serialized there, into
+/// the writer bridged to `std::io::Write`, so each call holds a blocking
+/// thread for the whole transfer. It must be called within a Tokio runtime,
+/// and panics otherwise.
+#[cfg(feature = "tokio")]
+pub async fn to_async_writer<W, T>(model: T, writer: W) -> Result<(), String>
+where
+  W: tokio::io::AsyncWrite + Unpin + Send + 'static,
+  T: YaSerialize + Send + 'static,
+{
+  to_async_writer_with_config(model, writer, &Config::default()).await
+}
+
+/// Serialize XML into an async writer with control on formatting, on a
+/// blocking thread like `to_async_writer`
+#[cfg(feature = "tokio")]
+pub async fn to_async_writer_with_config<W, T>(
+  model: T,
+  writer: W,
+  config: &Config,
+) -> Result<(), String>
+where
+  W: tokio::io::AsyncWrite + Unpin + Send + 'static,
+  T: YaSerialize + Send + 'static,
+{
+  let writer = tokio_util::io::SyncIoBridge::new(writer);
+  let config = config.clone();
+
+  tokio::task::spawn_blocking(move || {
+    let mut writer = serialize_with_writer(&model, writer, &config)?;
+    writer.flush().map_err(|e| e.to_string())
+  })
+  .await
+  .map_err(|e| e.to_string())?
 }
 
 pub fn serialize_with_writer<W: Write, T: YaSerialize>(
@@ -28,18 +91,59 @@ pub fn serialize_with_writer<W: Write, T: YaSerialize>(
   writer: W,
   config: &Config,
 ) -> Result<W, String> {
+  let writer = Output::new(writer, config);
   let mut serializer = Serializer::new_from_writer(writer, config);
-  match YaSerialize::serialize(model, &mut serializer) {
-    Ok(()) => Ok(serializer.into_inner()),
-    Err(msg) => Err(msg),
+  if config.hoist_namespaces {
+    serializer.root_namespaces = collect_namespaces(model, config)?;
+  }
+  YaSerialize::serialize(model, &mut serializer)?;
+
+  let mut writer = serializer.into_inner();
+  if config.trailing_newline {
+    writer
+      .write_all(config.line_ending.as_str().as_bytes())
+      .map_err(|e| e.to_string())?;
   }
+  writer.finish().map_err(|e| e.to_string())
+}
+
+/// Serialize `model` without output to find the namespaces it declares.
+/// Prefixes bound to several URIs are left out.
+fn collect_namespaces<T: YaSerialize>(model: &T, config: &Config) -> Result<Namespace, String> {
+  let writer = Output::new(std::io::sink(), config);
+  let mut serializer = Serializer::new_from_writer(writer, config);
+  serializer.collected_namespaces = Some(BTreeMap::new());
+  YaSerialize::serialize(model, &mut serializer)?;
+
+  let mut namespace = Namespace::empty();
+  for (prefix, uri) in serializer.collected_namespaces.unwrap_or_default() {
+    if let Some(uri) = uri {
+      namespace.put(prefix, uri);
+    }
+  }
+  Ok(namespace)
 }
 
 pub fn to_string_content<T: YaSerialize>(model: &T) -> Result<String, String> {
-  let buf = Cursor::new(Vec::new());
-  let cursor = serialize_with_writer_content(model, buf)?;
-  let data = str::from_utf8(cursor.get_ref()).expect("Found invalid UTF-8");
-  Ok(data.into())
+  let buf = serialize_with_writer_content(model, Vec::new())?;
+  into_utf8_string(buf)
+}
+
+/// Serialize the content of each item, joined with the separator, into one attribute value
+pub fn to_attribute_value<'a, T: YaSerialize + 'a>(
+  items: impl IntoIterator<Item = &'a T>,
+  separator: &str,
+) -> Result<String, String> {
+  let mut buf = Vec::new();
+
+  for (index, item) in items.into_iter().enumerate() {
+    if index > 0 {
+      buf.extend_from_slice(separator.as_bytes());
+    }
+    buf = serialize_with_writer_content(item, buf)?;
+  }
+
+  into_utf8_string(buf)
 }
 
 pub fn serialize_with_writer_content<W: Write, T: YaSerialize>(
@@ -54,10 +158,36 @@ pub fn serialize_with_writer_content<W: Write, T: YaSerialize>(
   }
 }
 
+fn into_utf8_string(buf: Vec<u8>) -> Result<String, String> {
+  String::from_utf8(buf).map_err(|e| format!("Found invalid UTF-8: {}", e))
+}
+
 pub struct Serializer<W: Write> {
   writer: EventWriter<W>,
   skip_start_end: bool,
   start_event_name: Option<String>,
+  depth: usize,
+  /// Default rendering of elements without content, `None` to leave it to xml-rs
+  default_empty_element: Option<EmptyElement>,
+  /// Rendering set for the elements written at each depth
+  empty_elements: Vec<Option<EmptyElement>>,
+  /// Rendering of the element just started, while it has no content
+  open_empty_element: Option<EmptyElement>,
+  /// Custom declaration and prolog, written before the first event
+  prolog: Option<String>,
+  /// Prefix written for each namespace URI
+  prefixes: BTreeMap<String, String>,
+  /// Namespace URI written for each declared one
+  namespace_aliases: BTreeMap<String, String>,
+  /// Namespaces declared by the events, to resolve the prefixes to rewrite
+  namespaces: NamespaceStack,
+  /// Namespaces declared in the document, once rewritten
+  written_namespaces: NamespaceStack,
+  /// Prefixed namespaces declared in the document, `None` for prefixes bound
+  /// to several URIs, when collected for `Config::hoist_namespaces`
+  collected_namespaces: Option<BTreeMap<String, Option<String>>>,
+  /// Namespaces declared on the root element
+  root_namespaces: Namespace,
 }
 
 impl<W: Write> Serializer<W> {
@@ -66,6 +196,17 @@ impl<W: Write> Serializer<W> {
       writer,
       skip_start_end: false,
       start_event_name: None,
+      depth: 0,
+      default_empty_element: None,
+      empty_elements: vec![],
+      open_empty_element: None,
+      prolog: None,
+      prefixes: BTreeMap::new(),
+      namespace_aliases: BTreeMap::new(),
+      namespaces: NamespaceStack::empty(),
+      written_namespaces: NamespaceStack::empty(),
+      collected_namespaces: None,
+      root_namespaces: Namespace::empty(),
     }
   }
 
@@ -74,13 +215,38 @@ impl<W: Write> Serializer<W> {
       .cdata_to_characters(config.cdata_to_characters)
       .perform_indent(config.perform_indent)
       .pad_self_closing(config.pad_self_closing)
-      .write_document_declaration(config.write_document_declaration);
+      .line_separator(config.line_ending.as_str())
+      .write_document_declaration(false);
 
     if let Some(indent_string_value) = &config.indent_string {
       emitter_config = emitter_config.indent_string(indent_string_value.clone());
     }
 
-    Self::new(EventWriter::new_with_config(writer, emitter_config))
+    let mut serializer = Self::new(EventWriter::new_with_config(writer, emitter_config));
+    if !config.self_closing {
+      serializer.default_empty_element = Some(EmptyElement::Explicit);
+    }
+    serializer.prefixes = config.prefixes.clone();
+    serializer.namespace_aliases = config.namespace_aliases.clone();
+
+    let declaration = config
+      .write_document_declaration
+      .then(|| config.declaration());
+    let mut prolog = String::new();
+    for item in declaration
+      .iter()
+      .map(|declaration| declaration.to_string())
+      .chain(config.prolog.iter().map(|item| item.to_string()))
+    {
+      prolog.push_str(&item);
+      if config.perform_indent {
+        prolog.push_str(config.line_ending.as_str());
+      }
+    }
+    if !prolog.is_empty() {
+      serializer.prolog = Some(prolog);
+    }
+    serializer
   }
 
   pub fn new_for_inner(writer: W) -> Self {
@@ -109,20 +275,248 @@ impl<W: Write> Serializer<W> {
     self.start_event_name = name;
   }
 
+  pub fn get_empty_element(&self) -> Option<EmptyElement> {
+    self.empty_elements.get(self.depth).copied().flatten()
+  }
+
+  /// Set how the next elements written at the current depth render without
+  /// content, until reset with `None`.
+  pub fn set_empty_element(&mut self, empty_element: Option<EmptyElement>) {
+    if self.empty_elements.len() <= self.depth {
+      self.empty_elements.resize(self.depth + 1, None);
+    }
+    self.empty_elements[self.depth] = empty_element;
+  }
+
   pub fn write<'a, E>(&mut self, event: E) -> xml::writer::Result<()>
   where
     E: Into<XmlEvent<'a>>,
   {
-    self.writer.write(event)
+    let event = event.into();
+
+    if let Some(prolog) = self.prolog.take() {
+      self.writer.inner_mut().write_all(prolog.as_bytes())?;
+    }
+
+    match (&event, self.open_empty_element.take()) {
+      (XmlEvent::Characters(""), Some(EmptyElement::SelfClosing)) => {
+        self.open_empty_element = Some(EmptyElement::SelfClosing);
+        return Ok(());
+      }
+      (XmlEvent::EndElement { .. }, Some(EmptyElement::Explicit)) => {
+        self.writer.write(XmlEvent::characters(""))?;
+      }
+      _ => {}
+    }
+
+    match event {
+      XmlEvent::StartElement { .. } => {
+        self.open_empty_element = self.get_empty_element().or(self.default_empty_element);
+        self.depth += 1;
+      }
+      XmlEvent::EndElement { .. } => {
+        self.depth = self.depth.saturating_sub(1);
+        // Settings of the children no longer apply
+        self.empty_elements.truncate(self.depth + 1);
+      }
+      _ => {}
+    }
+
+    if self.prefixes.is_empty()
+      && self.namespace_aliases.is_empty()
+      && self.collected_namespaces.is_none()
+      && self.root_namespaces.is_empty()
+    {
+      return self.writer.write(event);
+    }
+
+    match event {
+      XmlEvent::StartElement {
+        name,
+        attributes,
+        namespace,
+      } => {
+        let (name, attributes, namespace) = self.reprefix(name, &attributes, &namespace);
+
+        self.writer.write(XmlEvent::StartElement {
+          name: name.borrow(),
+          attributes: attributes
+            .iter()
+            .map(|attribute| attribute.borrow())
+            .collect(),
+          namespace: Cow::Owned(namespace),
+        })
+      }
+      XmlEvent::EndElement { .. } => {
+        self.namespaces.try_pop();
+        self.written_namespaces.try_pop();
+        // xml-rs knows the name it wrote
+        self.writer.write(XmlEvent::EndElement { name: None })
+      }
+      event => self.writer.write(event),
+    }
+  }
+
+  /// Rewrite the prefixes of a start element with `Config::prefixes`, and
+  /// its namespace URIs with `Config::namespace_aliases`. Declare the
+  /// hoisted namespaces on the root element.
+  fn reprefix(
+    &mut self,
+    name: Name<'_>,
+    attributes: &[Attribute<'_>],
+    namespace: &Namespace,
+  ) -> (OwnedName, Vec<OwnedAttribute>, Namespace) {
+    self.namespaces.push_empty();
+    for (prefix, uri) in namespace {
+      self.namespaces.put(prefix, uri);
+    }
+
+    let aliases = &self.namespace_aliases;
+    let alias = |uri: &str| aliases.get(uri).map_or(uri, String::as_str).to_string();
+
+    let mut declarations = Namespace::empty();
+    for (prefix, uri) in namespace {
+      declarations.put(
+        self.prefixes.get(uri).map_or(prefix, String::as_str),
+        alias(uri),
+      );
+    }
+
+    let mut name = name.to_owned();
+    let uri = self
+      .namespaces
+      .get(name.prefix.as_deref().unwrap_or(NS_NO_PREFIX))
+      .unwrap_or_default();
+    if let Some(prefix) = self.prefixes.get(uri) {
+      name.prefix = (!prefix.is_empty()).then(|| prefix.clone());
+    }
+    let mut owned_attributes = Vec::with_capacity(attributes.len());
+    if name.prefix.is_none() {
+      // Another namespace may have become the default one
+      let default_uri = declarations
+        .get(NS_NO_PREFIX)
+        .or_else(|| self.written_namespaces.get(NS_NO_PREFIX))
+        .unwrap_or_default();
+      let uri = alias(uri);
+      if default_uri != uri {
+        declarations.put(NS_NO_PREFIX, uri.as_str());
+        if uri.is_empty() {
+          // Not written by xml-rs
+          owned_attributes.push(OwnedAttribute::new(OwnedName::local("xmlns"), ""));
+        }
+      }
+    }
+
+    for attribute in attributes {
+      let mut attribute = attribute.to_owned();
+      if let Some(prefix) = attribute.name.prefix.clone() {
+        match self
+          .namespaces
+          .get(&prefix)
+          .map(|uri| (uri, self.prefixes.get(uri)))
+        {
+          // Attributes need a prefix to be in a namespace
+          Some((uri, Some(new_prefix))) if new_prefix.is_empty() => {
+            declarations.put(prefix, alias(uri));
+          }
+          Some((_, Some(new_prefix))) => attribute.name.prefix = Some(new_prefix.clone()),
+          _ => {}
+        }
+      }
+      owned_attributes.push(attribute);
+    }
+
+    if let Some(collected) = &mut self.collected_namespaces {
+      for (prefix, uri) in &declarations {
+        if prefix == NS_NO_PREFIX {
+          // Hoisted, it would apply to unprefixed elements of no namespace
+          continue;
+        }
+        collected
+          .entry(prefix.to_string())
+          .and_modify(|bound| {
+            if bound.as_deref() != Some(uri) {
+              *bound = None;
+            }
+          })
+          .or_insert_with(|| Some(uri.to_string()));
+      }
+    }
+    if self.depth == 1 {
+      for (prefix, uri) in &self.root_namespaces {
+        declarations.put(prefix, uri);
+      }
+    }
+
+    self.written_namespaces.push_empty();
+    for (prefix, uri) in &declarations {
+      self.written_namespaces.put(prefix, uri);
+    }
+
+    (name, owned_attributes, declarations)
   }
 }
 
+/// How an element without content is written
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmptyElement {
+  /// `<a />`
+  SelfClosing,
+  /// `<a></a>`
+  Explicit,
+}
+
+#[derive(Clone)]
 pub struct Config {
   pub perform_indent: bool,
   pub write_document_declaration: bool,
+  /// Declaration written instead of `<?xml version="1.0" encoding="utf-8"?>`
+  pub declaration: Option<Declaration>,
+  /// Processing instructions and DOCTYPE written before the root element
+  pub prolog: Vec<Prolog>,
   pub indent_string: Option<String>,
+  /// Write `cdata` fields as escaped text rather than CDATA sections
   pub cdata_to_characters: bool,
   pub pad_self_closing: bool,
+  /// Quote around attribute values, also used in the document declaration
+  pub attribute_quote: QuoteStyle,
+  /// Write elements without content as `<a />` rather than `<a></a>`
+  pub self_closing: bool,
+  /// Line ending written by indentation and the trailing newline
+  pub line_ending: LineEnding,
+  /// End the document with a line ending
+  pub trailing_newline: bool,
+  /// Prefix written for each namespace URI instead of the one declared with
+  /// `#[yaserde(namespaces)]`, an empty one for the default namespace.
+  /// `Deserializer::prefixes()` returns those of a document.
+  pub prefixes: BTreeMap<String, String>,
+  /// Namespace URI written instead of one declared with
+  /// `#[yaserde(namespaces)]`, as declared URI to written URI
+  pub namespace_aliases: BTreeMap<String, String>,
+  /// Declare the prefixed namespaces of the whole document on its root
+  /// element, rather than on the elements using them. The model is
+  /// serialized twice to find them.
+  pub hoist_namespaces: bool,
+  /// Encoding of the document, declared unless `declaration` says otherwise.
+  /// Characters it can't represent are written as character references.
+  #[cfg(feature = "encoding_rs")]
+  pub encoding: &'static encoding_rs::Encoding,
+}
+
+impl Config {
+  /// Declaration to write. It is written here rather than by xml-rs, which
+  /// would put it after the comments and the prolog.
+  fn declaration(&self) -> Declaration {
+    #[cfg(feature = "encoding_rs")]
+    if self.declaration.is_none() && self.encoding != encoding_rs::UTF_8 {
+      return Declaration {
+        encoding: Some(self.encoding.name().to_string()),
+        ..Default::default()
+      };
+    }
+
+    self.declaration.clone().unwrap_or_default()
+  }
 }
 
 impl Default for Config {
@@ -130,9 +524,45 @@ impl Default for Config {
     Config {
       perform_indent: false,
       write_document_declaration: true,
+      declaration: None,
+      prolog: vec![],
       indent_string: None,
-      cdata_to_characters: true,
+      cdata_to_characters: false,
       pad_self_closing: true,
+      attribute_quote: QuoteStyle::Double,
+      self_closing: true,
+      line_ending: LineEnding::Lf,
+      trailing_newline: false,
+      prefixes: BTreeMap::new(),
+      namespace_aliases: BTreeMap::new(),
+      hoist_namespaces: false,
+      #[cfg(feature = "encoding_rs")]
+      encoding: encoding_rs::UTF_8,
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuoteStyle {
+  /// `name="value"`
+  Double,
+  /// `name='value'`
+  Single,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineEnding {
+  /// `\n`
+  Lf,
+  /// `\r\n`
+  CrLf,
+}
+
+impl LineEnding {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      LineEnding::Lf => "\n",
+      LineEnding::CrLf => "\r\n",
     }
   }
 }
@@ -0,0 +1,226 @@
+//! Output sink applying the formatting xml-rs can't be configured with.
+
+use super::{Config, QuoteStyle};
+#[cfg(feature = "encoding_rs")]
+use encoding_rs::{CoderResult, Encoder, Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use std::io::{Result, Write};
+
+enum State {
+  Text,
+  /// After `<`
+  Open,
+  /// After `<!`
+  Bang,
+  /// After `<?`, with the number of bytes matching `xml`
+  Instruction(usize),
+  /// Inside a tag or the XML declaration, where quotes are rewritten
+  Tag,
+  /// Inside markup copied as is, until the end delimiter
+  Skip {
+    end: &'static [u8],
+    matched: usize,
+  },
+}
+
+pub(crate) struct Output<W: Write> {
+  inner: W,
+  quote: QuoteStyle,
+  state: State,
+  #[cfg(feature = "encoding_rs")]
+  transcoder: Option<Transcoder>,
+}
+
+impl<W: Write> Output<W> {
+  pub(crate) fn new(inner: W, config: &Config) -> Self {
+    Output {
+      inner,
+      quote: config.attribute_quote,
+      state: State::Text,
+      #[cfg(feature = "encoding_rs")]
+      transcoder: Transcoder::new(config.encoding),
+    }
+  }
+
+  /// Flush the encoder state and return the writer
+  pub(crate) fn finish(self) -> Result<W> {
+    #[cfg(feature = "encoding_rs")]
+    if let Some(mut transcoder) = self.transcoder {
+      let mut inner = self.inner;
+      let mut buf = vec![];
+      transcoder.transcode(&[], &mut buf, true)?;
+      inner.write_all(&buf)?;
+      return Ok(inner);
+    }
+
+    Ok(self.inner)
+  }
+
+  /// Return the byte to write. xml-rs escapes quotes inside attribute values,
+  /// so every raw `"` in a tag delimits a value.
+  fn single_quote(&mut self, byte: u8) -> u8 {
+    self.state = match (&self.state, byte) {
+      (State::Text, b'<') => State::Open,
+      (State::Text, _) => State::Text,
+      (State::Open, b'!') => State::Bang,
+      (State::Open, b'?') => State::Instruction(0),
+      (State::Open, _) | (State::Tag, _) if byte != b'>' => State::Tag,
+      (State::Open, _) | (State::Tag, _) => State::Text,
+      (State::Bang, b'-') => skip(b"-->"),
+      (State::Bang, b'[') => skip(b"]]>"),
+      (State::Bang, _) => skip(b">"),
+      (State::Instruction(3), b' ' | b'\t' | b'\r' | b'\n') => State::Tag,
+      (State::Instruction(matched), _) if b"xml".get(*matched) == Some(&byte) => {
+        State::Instruction(matched + 1)
+      }
+      (State::Instruction(_), _) => skip(b"?>"),
+      (State::Skip { end, matched }, _) => {
+        let matched = if end[*matched] == byte {
+          matched + 1
+        } else if end[0] == byte {
+          (*matched).max(1)
+        } else {
+          0
+        };
+
+        if matched == end.len() {
+          State::Text
+        } else {
+          State::Skip { end, matched }
+        }
+      }
+    };
+
+    match (&self.state, byte) {
+      (State::Tag, b'"') => b'\'',
+      _ => byte,
+    }
+  }
+}
+
+fn skip(end: &'static [u8]) -> State {
+  State::Skip { end, matched: 0 }
+}
+
+impl<W: Write> Write for Output<W> {
+  fn write(&mut self, buf: &[u8]) -> Result<usize> {
+    let quoted: Vec<u8>;
+    let output = match self.quote {
+      QuoteStyle::Double => buf,
+      QuoteStyle::Single => {
+        quoted = buf.iter().map(|byte| self.single_quote(*byte)).collect();
+        &quoted
+      }
+    };
+
+    #[cfg(feature = "encoding_rs")]
+    if let Some(transcoder) = &mut self.transcoder {
+      let mut encoded = vec![];
+      transcoder.transcode(output, &mut encoded, false)?;
+      self.inner.write_all(&encoded)?;
+      return Ok(buf.len());
+    }
+
+    self.inner.write_all(output)?;
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> Result<()> {
+    self.inner.flush()
+  }
+}
+
+/// Encode the UTF-8 written by xml-rs. encoding_rs only decodes UTF-16,
+/// so it is encoded here, starting with a byte order mark.
+#[cfg(feature = "encoding_rs")]
+struct Transcoder {
+  encoding: &'static Encoding,
+  encoder: Encoder,
+  /// Incomplete UTF-8 sequence ending the last write
+  pending: Vec<u8>,
+  started: bool,
+}
+
+#[cfg(feature = "encoding_rs")]
+impl Transcoder {
+  fn new(encoding: &'static Encoding) -> Option<Self> {
+    (encoding != UTF_8).then(|| Transcoder {
+      encoding,
+      encoder: encoding.new_encoder(),
+      pending: vec![],
+      started: false,
+    })
+  }
+
+  fn transcode(&mut self, buf: &[u8], output: &mut Vec<u8>, last: bool) -> Result<()> {
+    use std::io::{Error, ErrorKind};
+
+    self.pending.extend_from_slice(buf);
+    let valid = match std::str::from_utf8(&self.pending) {
+      Ok(text) => text.len(),
+      Err(error) if error.error_len().is_none() && !last => error.valid_up_to(),
+      Err(error) => return Err(Error::new(ErrorKind::InvalidData, error)),
+    };
+    let mut text = std::str::from_utf8(&self.pending[..valid])
+      .map_err(|error| Error::new(ErrorKind::InvalidData, error))?;
+
+    if self.encoding == UTF_16LE || self.encoding == UTF_16BE {
+      let big_endian = self.encoding == UTF_16BE;
+      let units = (!self.started)
+        .then_some('\u{feff}' as u16)
+        .into_iter()
+        .chain(text.encode_utf16());
+      for unit in units {
+        if big_endian {
+          output.extend_from_slice(&unit.to_be_bytes());
+        } else {
+          output.extend_from_slice(&unit.to_le_bytes());
+        }
+      }
+    } else {
+      loop {
+        output.reserve(
+          self
+            .encoder
+            .max_buffer_length_from_utf8_if_no_unmappables(text.len())
+            .unwrap_or(text.len() * 4)
+            + 16,
+        );
+        let (result, read, _replaced) = self.encoder.encode_from_utf8_to_vec(text, output, last);
+        text = &text[read..];
+        if result == CoderResult::InputEmpty {
+          break;
+        }
+      }
+    }
+
+    self.started = true;
+    self.pending.drain(..valid);
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn single_quotes_only_in_tags() {
+    let content =
+      r#"<?xml version="1.0"?><?pi a="b"?><a b="c"><!-- "d" --><![CDATA["e"]]>"f"<g/></a>"#;
+
+    let config = Config {
+      attribute_quote: QuoteStyle::Single,
+      ..Default::default()
+    };
+    let mut output = Output::new(vec![], &config);
+    // Split writes keep the state
+    for chunk in content.as_bytes().chunks(3) {
+      output.write_all(chunk).unwrap();
+    }
+
+    assert_eq!(
+      String::from_utf8(output.finish().unwrap()).unwrap(),
+      r#"<?xml version='1.0'?><?pi a="b"?><a b='c'><!-- "d" --><![CDATA["e"]]>"f"<g/></a>"#
+    );
+  }
+}
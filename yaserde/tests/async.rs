@@ -0,0 +1,103 @@
+#![cfg(feature = "tokio")]
+
+#[macro_use]
+extern crate yaserde_derive;
+
+use tokio::io::AsyncWriteExt;
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+#[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+#[yaserde(rename = "book")]
+pub struct Book {
+  #[yaserde(attribute = true)]
+  isbn: String,
+  author: String,
+  title: String,
+}
+
+fn model() -> Book {
+  Book {
+    isbn: "978-2-07-040850-4".to_string(),
+    author: "Antoine de Saint-Exupéry".to_string(),
+    title: "Le Petit Prince".to_string(),
+  }
+}
+
+#[tokio::test]
+async fn async_round_trip() {
+  init();
+
+  let (client, server) = tokio::io::duplex(16);
+
+  let (written, loaded) = tokio::join!(
+    yaserde::ser::to_async_writer(model(), client),
+    yaserde::de::from_async_reader::<_, Book>(server),
+  );
+
+  assert_eq!(written, Ok(()));
+  assert_eq!(loaded, Ok(model()));
+}
+
+#[tokio::test]
+async fn async_round_trip_with_config() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "library")]
+  pub struct Library {
+    #[yaserde(rename = "book")]
+    books: Vec<Book>,
+  }
+
+  let library = || Library {
+    books: (0..1_000).map(|_| model()).collect(),
+  };
+
+  // Much larger than the pipe, written while it is read
+  let (client, server) = tokio::io::duplex(64);
+  let ser_config = yaserde::ser::Config {
+    perform_indent: true,
+    ..Default::default()
+  };
+  let de_config = yaserde::de::Config {
+    max_depth: Some(3),
+    ..Default::default()
+  };
+
+  let (written, loaded) = tokio::join!(
+    yaserde::ser::to_async_writer_with_config(library(), client, &ser_config),
+    yaserde::de::from_async_reader_with_config::<_, Library>(server, &de_config),
+  );
+
+  assert_eq!(written, Ok(()));
+  assert_eq!(loaded, Ok(library()));
+}
+
+#[tokio::test]
+async fn async_read_in_chunks() {
+  init();
+
+  let content = r#"<book isbn="0"><author>Someone</author><title>Something</title></book>"#;
+  let (mut client, server) = tokio::io::duplex(8);
+
+  let writer = tokio::spawn(async move {
+    for chunk in content.as_bytes().chunks(5) {
+      client.write_all(chunk).await.unwrap();
+    }
+  });
+
+  let loaded: Result<Book, String> = yaserde::de::from_async_reader(server).await;
+  writer.await.unwrap();
+
+  assert_eq!(
+    loaded,
+    Ok(Book {
+      isbn: "0".to_string(),
+      author: "Someone".to_string(),
+      title: "Something".to_string(),
+    })
+  );
+}
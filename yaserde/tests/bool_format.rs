@@ -0,0 +1,139 @@
+#[macro_use]
+extern crate yaserde;
+#[macro_use]
+extern crate yaserde_derive;
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+#[test]
+fn bool_format_field() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "interface")]
+  pub struct Interface {
+    #[yaserde(attribute = true, bool_format = "yesno")]
+    enabled: bool,
+    #[yaserde(attribute = true, bool_format = "onoff")]
+    dhcp: Option<bool>,
+    #[yaserde(bool_format = "int")]
+    blockpriv: bool,
+    #[yaserde(bool_format = "int")]
+    blockbogons: Option<bool>,
+    #[yaserde(bool_format = "onoff")]
+    lanes: Vec<bool>,
+    spoofmac: bool,
+  }
+
+  let model = Interface {
+    enabled: true,
+    dhcp: Some(false),
+    blockpriv: true,
+    blockbogons: Some(false),
+    lanes: vec![true, false],
+    spoofmac: false,
+  };
+
+  let content = r#"
+    <interface enabled="yes" dhcp="off">
+      <blockpriv>1</blockpriv>
+      <blockbogons>0</blockbogons>
+      <lanes>on</lanes>
+      <lanes>off</lanes>
+      <spoofmac>false</spoofmac>
+    </interface>"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Interface);
+
+  let loaded: Result<Interface, String> = yaserde::de::from_str(
+    r#"<interface enabled="maybe"><blockpriv>1</blockpriv><spoofmac>0</spoofmac></interface>"#,
+  );
+  assert_eq!(
+    loaded,
+    Err(r#"invalid boolean "maybe", expected "yes" or "no""#.to_string())
+  );
+}
+
+#[test]
+fn bool_format_container() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "rule", bool_format = "int")]
+  pub struct Rule {
+    #[yaserde(attribute = true)]
+    disabled: bool,
+    #[yaserde(attribute = true, separator = ",")]
+    flags: Vec<bool>,
+    log: bool,
+    #[yaserde(bool_format = "bool")]
+    quick: bool,
+  }
+
+  let model = Rule {
+    disabled: false,
+    flags: vec![true, true, false],
+    log: true,
+    quick: true,
+  };
+
+  let content = r#"<rule disabled="0" flags="1,1,0"><log>1</log><quick>true</quick></rule>"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Rule);
+}
+
+#[test]
+fn bool_format_text() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "option")]
+  pub struct Setting {
+    #[yaserde(attribute = true)]
+    name: String,
+    #[yaserde(text = true, bool_format = "yesno")]
+    value: bool,
+  }
+
+  let model = Setting {
+    name: "forwarding".to_string(),
+    value: true,
+  };
+
+  let content = r#"<option name="forwarding">yes</option>"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Setting);
+}
+
+#[test]
+fn bool_format_enum() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "base")]
+  pub struct Base {
+    value: Value,
+  }
+
+  #[derive(Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(bool_format = "onoff")]
+  pub enum Value {
+    #[default]
+    Empty,
+    Flag(bool),
+  }
+
+  let model = Base {
+    value: Value::Flag(true),
+  };
+
+  let content = "<base><value><Flag>on</Flag></value></base>";
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Base);
+}
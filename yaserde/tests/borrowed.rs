@@ -0,0 +1,168 @@
+#[macro_use]
+extern crate yaserde;
+#[macro_use]
+extern crate yaserde_derive;
+
+use std::borrow::Cow;
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+#[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+#[yaserde(rename = "library")]
+pub struct Library<'a> {
+  #[yaserde(attribute = true)]
+  name: Cow<'a, str>,
+  book: Vec<Book<'a>>,
+}
+
+#[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+#[yaserde(rename = "book")]
+pub struct Book<'a> {
+  #[yaserde(attribute = true)]
+  isbn: &'a str,
+  title: Cow<'a, str>,
+  author: Option<&'a str>,
+  tag: Vec<Cow<'a, str>>,
+  note: Note<'a>,
+}
+
+#[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+#[yaserde(rename = "note")]
+pub struct Note<'a> {
+  #[yaserde(text = true)]
+  content: &'a str,
+}
+
+#[test]
+#[cfg(feature = "quick-xml")]
+fn borrowed_fields() {
+  init();
+
+  let content = r#"
+    <library name="Public">
+      <book isbn="978-2-07-040850-4">
+        <title>Le Petit Prince</title>
+        <author>Antoine de Saint-Exupéry</author>
+        <tag>tale</tag>
+        <tag>aviation</tag>
+        <note>Read it twice</note>
+      </book>
+      <book isbn="978-2-07-036822-8">
+        <title>Vol de nuit &amp; Courrier sud</title>
+        <note>Night mail</note>
+      </book>
+    </library>"#;
+
+  let model = Library {
+    name: "Public".into(),
+    book: vec![
+      Book {
+        isbn: "978-2-07-040850-4",
+        title: "Le Petit Prince".into(),
+        author: Some("Antoine de Saint-Exupéry"),
+        tag: vec!["tale".into(), "aviation".into()],
+        note: Note {
+          content: "Read it twice",
+        },
+      },
+      Book {
+        isbn: "978-2-07-036822-8",
+        title: "Vol de nuit & Courrier sud".into(),
+        author: None,
+        tag: vec![],
+        note: Note {
+          content: "Night mail",
+        },
+      },
+    ],
+  };
+
+  let loaded: Library =
+    yaserde::de::from_str_with_parser(content, yaserde::de::Parser::QuickXml).unwrap();
+  assert_eq!(loaded, model);
+  assert!(matches!(loaded.name, Cow::Borrowed(_)));
+  assert!(matches!(loaded.book[0].title, Cow::Borrowed(_)));
+  assert!(matches!(loaded.book[0].tag[0], Cow::Borrowed(_)));
+  // Escaped text has to be copied
+  assert!(matches!(loaded.book[1].title, Cow::Owned(_)));
+}
+
+#[test]
+fn borrowed_fields_serialize() {
+  init();
+
+  let model = Book {
+    isbn: "978-2-07-036822-8",
+    title: Cow::Owned("Vol de nuit & Courrier sud".to_string()),
+    author: None,
+    tag: vec!["night".into()],
+    note: Note {
+      content: "Night mail",
+    },
+  };
+
+  let content = r#"<book isbn="978-2-07-036822-8"><title>Vol de nuit &amp; Courrier sud</title><tag>night</tag><note>Night mail</note></book>"#;
+
+  serialize_and_validate!(model, content);
+}
+
+#[test]
+fn borrowed_fields_xml_rs() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize)]
+  #[yaserde(rename = "book")]
+  pub struct Book<'a> {
+    #[yaserde(attribute = true)]
+    isbn: Cow<'a, str>,
+    title: Cow<'a, str>,
+  }
+
+  // xml-rs always allocates, so `Cow` fields are owned and `&str` fields fail
+  let loaded: Book = yaserde::de::from_str(
+    r#"<book isbn="978-2-07-040850-4"><title>Le Petit Prince</title></book>"#,
+  )
+  .unwrap();
+  assert_eq!(loaded.isbn, "978-2-07-040850-4");
+  assert_eq!(loaded.title, "Le Petit Prince");
+
+  let loaded: Result<Note, String> = yaserde::de::from_str("<note>Read it twice</note>");
+  assert_eq!(
+    loaded,
+    Err(
+      r#"unable to borrow "Read it twice" from the input, &str fields need Parser::QuickXml and text without escapes"#
+        .to_string()
+    )
+  );
+}
+
+#[test]
+fn own_cow_type() {
+  init();
+
+  // Not `std::borrow::Cow<str>`, read as a struct
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "cow")]
+  pub struct Cow {
+    #[yaserde(attribute = true)]
+    name: String,
+  }
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "farm")]
+  pub struct Farm {
+    cow: Cow,
+  }
+
+  let model = Farm {
+    cow: Cow {
+      name: "Marguerite".to_string(),
+    },
+  };
+  let content = r#"<farm><cow name="Marguerite" /></farm>"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Farm);
+}
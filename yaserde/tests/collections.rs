@@ -0,0 +1,147 @@
+#[macro_use]
+extern crate yaserde;
+#[macro_use]
+extern crate yaserde_derive;
+
+use std::collections::{BTreeSet, HashSet, LinkedList, VecDeque};
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+#[test]
+fn collection_types() {
+  init();
+
+  test_for_type!(VecDeque::<String>, VecDeque::new(), None);
+  test_for_type!(
+    VecDeque::<String>,
+    VecDeque::from(vec!["test".to_string()]),
+    Some("test")
+  );
+  test_for_type!(LinkedList::<u8>, LinkedList::from([12_u8]), Some("12"));
+  test_for_type!(HashSet::<String>, HashSet::new(), None);
+  test_for_type!(
+    HashSet::<String>,
+    HashSet::from(["test".to_string()]),
+    Some("test")
+  );
+  test_for_type!(BTreeSet::<u32>, BTreeSet::from([12_u32]), Some("12"));
+  test_for_type!(
+    std::collections::btree_set::BTreeSet::<u32>,
+    BTreeSet::from([12_u32]),
+    Some("12")
+  );
+}
+
+#[test]
+fn collection_of_elements() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "config")]
+  pub struct Config {
+    tags: BTreeSet<String>,
+    queue: VecDeque<Job>,
+  }
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "job")]
+  pub struct Job {
+    #[yaserde(attribute = true)]
+    id: u32,
+  }
+
+  let model = Config {
+    tags: BTreeSet::from(["blue".to_string(), "green".to_string()]),
+    queue: VecDeque::from(vec![Job { id: 2 }, Job { id: 1 }]),
+  };
+
+  let content = r#"
+    <config>
+      <tags>blue</tags>
+      <tags>green</tags>
+      <queue id="2" />
+      <queue id="1" />
+    </config>"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Config);
+
+  let content = r#"
+    <config>
+      <tags>green</tags>
+      <tags>blue</tags>
+      <tags>green</tags>
+    </config>"#;
+
+  let model = Config {
+    tags: BTreeSet::from(["blue".to_string(), "green".to_string()]),
+    queue: VecDeque::new(),
+  };
+
+  deserialize_and_validate!(content, model, Config);
+}
+
+#[test]
+fn collection_of_attributes() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "rule")]
+  pub struct Rule {
+    #[yaserde(attribute = true)]
+    ports: BTreeSet<u16>,
+    #[yaserde(attribute = true)]
+    names: Vec<String>,
+    #[yaserde(attribute = true)]
+    flags: HashSet<String>,
+  }
+
+  let model = Rule {
+    ports: BTreeSet::from([22, 80, 443]),
+    names: vec!["ssh".to_string(), "http".to_string()],
+    flags: HashSet::from(["log".to_string()]),
+  };
+
+  let content = r#"<rule ports="22 80 443" names="ssh http" flags="log" />"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Rule);
+
+  let model = Rule {
+    ports: BTreeSet::from([22, 80]),
+    names: vec![],
+    flags: HashSet::new(),
+  };
+
+  serialize_and_validate!(model, r#"<rule ports="22 80" />"#);
+  deserialize_and_validate!(r#"<rule ports="80 22 80" />"#, model, Rule);
+}
+
+#[test]
+fn collection_in_enum() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "base")]
+  pub struct Base {
+    color: Color,
+  }
+
+  #[derive(Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+  pub enum Color {
+    #[default]
+    None,
+    Channels(VecDeque<u8>),
+  }
+
+  let model = Base {
+    color: Color::Channels(VecDeque::from(vec![1, 2])),
+  };
+
+  let content = "<base><color><Channels>1</Channels><Channels>2</Channels></color></base>";
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Base);
+}
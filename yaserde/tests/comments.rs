@@ -0,0 +1,100 @@
+#[macro_use]
+extern crate yaserde;
+#[macro_use]
+extern crate yaserde_derive;
+
+use yaserde::de::{Config, Deserializer};
+use yaserde::YaDeserialize;
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+#[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+#[yaserde(rename = "server")]
+pub struct Server {
+  #[yaserde(comments = true)]
+  comments: Vec<String>,
+  #[yaserde(attribute = true)]
+  name: String,
+  port: u16,
+}
+
+#[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+#[yaserde(rename = "config")]
+pub struct Settings {
+  #[yaserde(comments = true)]
+  comments: Vec<String>,
+  server: Vec<Server>,
+}
+
+const CONTENT: &str = r#"<?xml version="1.0"?>
+<!-- Edited by hand -->
+<config>
+  <!-- Primary -->
+  <!-- Keep the default port -->
+  <server name="a">
+    <port>80</port>
+    <!-- Trailing -->
+  </server>
+  <server name="b"><!-- Not leading any element --><port>8080</port></server>
+</config>"#;
+
+fn model() -> Settings {
+  Settings {
+    comments: vec![" Edited by hand ".to_string()],
+    server: vec![
+      Server {
+        comments: vec![
+          " Primary ".to_string(),
+          " Keep the default port ".to_string(),
+        ],
+        name: "a".to_string(),
+        port: 80,
+      },
+      Server {
+        comments: vec![],
+        name: "b".to_string(),
+        port: 8080,
+      },
+    ],
+  }
+}
+
+#[test]
+fn comments_field() {
+  init();
+
+  let config = Config {
+    comments: true,
+    ..Default::default()
+  };
+  let mut deserializer = Deserializer::new_from_reader_with_config(CONTENT.as_bytes(), &config);
+  assert_eq!(Settings::deserialize(&mut deserializer), Ok(model()));
+
+  let content = r#"<!-- Edited by hand --><config><!-- Primary --><!-- Keep the default port --><server name="a"><port>80</port></server><server name="b"><port>8080</port></server></config>"#;
+  serialize_and_validate!(model(), content);
+}
+
+#[test]
+fn comments_skipped_by_default() {
+  init();
+
+  let loaded: Settings = yaserde::de::from_str(CONTENT).unwrap();
+  assert!(loaded.comments.is_empty());
+  assert!(loaded.server[0].comments.is_empty());
+}
+
+#[test]
+#[cfg(feature = "quick-xml")]
+fn comments_field_quick_xml() {
+  init();
+
+  let config = Config {
+    parser: yaserde::de::Parser::QuickXml,
+    comments: true,
+    ..Default::default()
+  };
+  let mut deserializer = Deserializer::new_from_str_with_config(CONTENT, &config);
+  assert_eq!(Settings::deserialize(&mut deserializer), Ok(model()));
+}
@@ -0,0 +1,136 @@
+#[macro_use]
+extern crate yaserde_derive;
+
+use yaserde::de::{
+  from_reader_with_config, from_str_with_config, Config, Error, Limit, UnknownElements,
+};
+use yaserde::RawXml;
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+#[derive(Debug, Default, PartialEq, YaDeserialize)]
+#[yaserde(rename = "book")]
+pub struct Book {
+  title: String,
+  #[yaserde(rename = "abstract")]
+  summary: Option<String>,
+}
+
+#[test]
+fn unknown_elements() {
+  init();
+
+  let content = r#"<book><edition year="2001"><isbn>1</isbn><empty/></edition><title>Dune</title><other/></book>"#;
+
+  let loaded: Result<Book, String> = yaserde::de::from_str(content);
+  assert_eq!(
+    loaded,
+    Err("Found unauthorized element edition".to_string())
+  );
+
+  let config = Config {
+    unknown_elements: UnknownElements::Skip,
+    ..Default::default()
+  };
+  let loaded: Book = from_str_with_config(content, &config).unwrap();
+  assert_eq!(
+    loaded,
+    Book {
+      title: "Dune".to_string(),
+      summary: None,
+    }
+  );
+
+  let loaded: Book = from_reader_with_config(content.as_bytes(), &config).unwrap();
+  assert_eq!(loaded.title, "Dune");
+}
+
+#[test]
+fn max_depth() {
+  init();
+
+  let config = Config {
+    max_depth: Some(2),
+    ..Default::default()
+  };
+
+  let loaded: Book = from_str_with_config("<book><title>Dune</title></book>", &config).unwrap();
+  assert_eq!(loaded.title, "Dune");
+
+  let loaded: Result<Book, Error> =
+    from_str_with_config("<book><title><b>Dune</b></title></book>", &config);
+  assert_eq!(loaded, Err(Error::LimitExceeded(Limit::Depth(2))));
+}
+
+#[test]
+fn cdata_sections() {
+  init();
+
+  let content =
+    "<book><title><![CDATA[ <Dune> ]]></title><abstract>Sand <![CDATA[&]]> worms</abstract></book>";
+
+  // Read as text and trimmed with it by default
+  let loaded: Book = yaserde::de::from_str(content).unwrap();
+  assert_eq!(loaded.title, "<Dune>");
+  assert_eq!(loaded.summary.as_deref(), Some("Sand & worms"));
+
+  let config = Config {
+    cdata_to_characters: false,
+    ..Default::default()
+  };
+  let loaded: Book = from_str_with_config(content, &config).unwrap();
+  assert_eq!(loaded.title, " <Dune> ");
+
+  let raw: RawXml = from_str_with_config("<title><![CDATA[<Dune>]]></title>", &config).unwrap();
+  assert_eq!(raw.0, "<title><![CDATA[<Dune>]]></title>");
+  let ser_config = yaserde::ser::Config {
+    write_document_declaration: false,
+    cdata_to_characters: false,
+    ..Default::default()
+  };
+  assert_eq!(
+    yaserde::ser::to_string_with_config(&raw, &ser_config).unwrap(),
+    "<title><![CDATA[<Dune>]]></title>"
+  );
+}
+
+#[test]
+#[cfg(feature = "quick-xml")]
+fn cdata_sections_quick_xml() {
+  init();
+
+  let config = Config {
+    parser: yaserde::de::Parser::QuickXml,
+    cdata_to_characters: false,
+    ..Default::default()
+  };
+
+  let raw: RawXml = from_str_with_config("<title>A<![CDATA[<Dune>]]></title>", &config).unwrap();
+  assert_eq!(raw.0, "<title>A<![CDATA[<Dune>]]></title>");
+}
+
+#[test]
+fn entity_expansion() {
+  init();
+
+  let content = r#"<!DOCTYPE book [<!ENTITY a "Arrakis"><!ENTITY b "&a;, &a;">]><book><title>&b;</title></book>"#;
+
+  let loaded: Book = yaserde::de::from_str(content).unwrap();
+  assert_eq!(loaded.title, "Arrakis, Arrakis");
+
+  let config = Config {
+    max_entity_expansion_length: 4,
+    ..Default::default()
+  };
+  let loaded: Result<Book, Error> = from_str_with_config(content, &config);
+  assert!(matches!(loaded, Err(Error::Message(_))));
+
+  let config = Config {
+    max_entity_expansion_depth: 1,
+    ..Default::default()
+  };
+  let loaded: Result<Book, Error> = from_str_with_config(content, &config);
+  assert!(matches!(loaded, Err(Error::Message(_))));
+}
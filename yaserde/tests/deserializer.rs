@@ -236,15 +236,17 @@ fn de_attributes_custom_deserializer() {
   mod other_mod {
     use super::*;
 
-    use xml::reader::XmlEvent;
+    use yaserde::events::ReadEvent as XmlEvent;
 
     #[derive(Debug, PartialEq)]
     pub struct Attributes {
       pub items: Vec<String>,
     }
 
-    impl YaDeserialize for Attributes {
-      fn deserialize<R: Read>(reader: &mut yaserde::de::Deserializer<R>) -> Result<Self, String> {
+    impl<'de> YaDeserialize<'de> for Attributes {
+      fn deserialize<R: Read>(
+        reader: &mut yaserde::de::Deserializer<'de, R>,
+      ) -> Result<Self, String> {
         loop {
           match reader.next_event()? {
             XmlEvent::StartElement { .. } => {}
@@ -493,6 +495,7 @@ fn de_enum() {
     Black,
   }
 
+  #[allow(dead_code)]
   #[derive(YaDeserialize, PartialEq, Debug)]
   pub struct RGBColor {
     red: String,
@@ -804,11 +807,13 @@ fn de_custom() {
     value: i32,
   }
 
-  impl YaDeserialize for Day {
-    fn deserialize<R: Read>(reader: &mut yaserde::de::Deserializer<R>) -> Result<Self, String> {
+  impl<'de> YaDeserialize<'de> for Day {
+    fn deserialize<R: Read>(
+      reader: &mut yaserde::de::Deserializer<'de, R>,
+    ) -> Result<Self, String> {
       use std::str::FromStr;
 
-      if let xml::reader::XmlEvent::StartElement { name, .. } = reader.peek()?.to_owned() {
+      if let yaserde::events::ReadEvent::StartElement { name, .. } = reader.peek()?.to_owned() {
         let expected_name = "Day".to_owned();
         if name.local_name != expected_name {
           return Err(format!(
@@ -821,7 +826,7 @@ fn de_custom() {
         return Err("StartElement missing".to_string());
       }
 
-      if let xml::reader::XmlEvent::Characters(text) = reader.peek()?.to_owned() {
+      if let yaserde::events::ReadEvent::Characters(text) = reader.peek()?.to_owned() {
         Ok(Day {
           value: 2 * i32::from_str(&text).unwrap(),
         })
@@ -853,7 +858,12 @@ fn de_subitem_issue_12() {
     id: i32,
   }
 
-  convert_and_validate!(
+  // Unknown elements are an error by default, see `de_strict`
+  let config = yaserde::de::Config {
+    unknown_elements: yaserde::de::UnknownElements::Skip,
+    ..Default::default()
+  };
+  let loaded: Result<Struct, _> = yaserde::de::from_str_with_config(
     r#"<?xml version="1.0" encoding="utf-8"?>
     <Struct>
       <id>54</id>
@@ -862,9 +872,9 @@ fn de_subitem_issue_12() {
       </SubStruct>
     </Struct>
     "#,
-    Struct,
-    Struct { id: 54 }
+    &config,
   );
+  assert_eq!(loaded, Ok(Struct { id: 54 }));
 }
 
 #[test]
@@ -910,15 +920,20 @@ fn de_subitem_issue_12_attributes() {
     id: i32,
   }
 
-  convert_and_validate!(
+  // Unknown elements are an error by default, see `de_strict`
+  let config = yaserde::de::Config {
+    unknown_elements: yaserde::de::UnknownElements::Skip,
+    ..Default::default()
+  };
+  let loaded: Result<Struct, _> = yaserde::de::from_str_with_config(
     r#"<?xml version="1.0" encoding="utf-8"?>
     <Struct id="54">
       <SubStruct id="86" />
     </Struct>
     "#,
-    Struct,
-    Struct { id: 54 }
+    &config,
   );
+  assert_eq!(loaded, Ok(Struct { id: 54 }));
 }
 
 #[test]
@@ -1107,6 +1122,7 @@ fn de_nested_macro_rules() {
 
   macro_rules! float_attrs {
     ($type:ty) => {
+      #[allow(dead_code)]
       #[derive(PartialEq, Debug, YaDeserialize)]
       pub struct Outer {
         #[yaserde(attribute = true)]
@@ -1150,3 +1166,80 @@ fn de_strict() {
   // assert!(false);
   assert_eq!(load.err().unwrap(), String::from("Found unauthorized element NonExistentAttrShouldCrash"));
 }
+
+#[test]
+fn de_iter() {
+  init();
+
+  #[derive(YaDeserialize, PartialEq, Debug)]
+  #[yaserde(rename = "record")]
+  pub struct Record {
+    #[yaserde(attribute = true)]
+    id: u32,
+    name: String,
+  }
+
+  let content = r#"<?xml version="1.0" encoding="utf-8"?>
+    <records>
+      <record id="1"><name>first</name></record>
+      <record id="2"><name>second</name></record>
+    </records>"#;
+
+  let mut deserializer = yaserde::de::Deserializer::new_from_reader(content.as_bytes());
+  let records: Result<Vec<Record>, String> = deserializer.iter::<Record>().collect();
+
+  assert_eq!(
+    records,
+    Ok(vec![
+      Record {
+        id: 1,
+        name: "first".to_string(),
+      },
+      Record {
+        id: 2,
+        name: "second".to_string(),
+      },
+    ])
+  );
+
+  let content = r#"<records><record id="1"><name>first</name></record><record id="x" /></records>"#;
+
+  let mut deserializer = yaserde::de::Deserializer::new_from_reader(content.as_bytes());
+  let mut records = deserializer.iter::<Record>();
+
+  assert_eq!(
+    records.next(),
+    Some(Ok(Record {
+      id: 1,
+      name: "first".to_string(),
+    }))
+  );
+  assert!(matches!(records.next(), Some(Err(_))));
+  assert_eq!(records.next(), None);
+}
+
+#[test]
+fn de_declaration() {
+  init();
+
+  #[derive(YaDeserialize, PartialEq, Debug)]
+  #[yaserde(rename = "base")]
+  pub struct XmlStruct {
+    item: String,
+  }
+
+  let content = r#"<?xml version="1.1" encoding="ISO-8859-1" standalone="yes"?><base><item>something</item></base>"#;
+
+  let mut deserializer = yaserde::de::Deserializer::new_from_reader(content.as_bytes());
+  let loaded = XmlStruct::deserialize(&mut deserializer).unwrap();
+
+  assert_eq!(loaded.item, "something");
+  assert_eq!(
+    deserializer.declaration(),
+    Some(&yaserde::events::Declaration {
+      version: yaserde::events::XmlVersion::Version11,
+      encoding: Some("ISO-8859-1".to_string()),
+      standalone: Some(true),
+    })
+  );
+}
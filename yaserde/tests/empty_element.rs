@@ -0,0 +1,128 @@
+#[macro_use]
+extern crate yaserde;
+#[macro_use]
+extern crate yaserde_derive;
+
+use yaserde::ser::EmptyElement;
+use yaserde::MaybeString;
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+#[derive(Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+pub struct Flag {
+  #[yaserde(attribute = true)]
+  name: Option<String>,
+}
+
+#[test]
+fn empty_element_field() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "base")]
+  pub struct XmlStruct {
+    #[yaserde(empty = "self_closing")]
+    title: String,
+    #[yaserde(empty = "explicit")]
+    flag: Flag,
+    #[yaserde(empty = "explicit")]
+    flags: Vec<Flag>,
+    other: Flag,
+    description: String,
+  }
+
+  let model = XmlStruct {
+    title: String::new(),
+    flag: Flag::default(),
+    flags: vec![
+      Flag::default(),
+      Flag {
+        name: Some("b".to_string()),
+      },
+    ],
+    other: Flag::default(),
+    description: String::new(),
+  };
+
+  let content = r#"<base><title /><flag></flag><flags></flags><flags name="b"></flags><other /><description></description></base>"#;
+  serialize_and_validate!(model, content);
+}
+
+#[test]
+fn empty_element_field_overrides_config() {
+  init();
+
+  #[derive(Debug, PartialEq, YaSerialize)]
+  #[yaserde(rename = "base")]
+  pub struct XmlStruct {
+    #[yaserde(empty = "self_closing")]
+    flag: Flag,
+    other: Flag,
+  }
+
+  let model = XmlStruct {
+    flag: Flag::default(),
+    other: Flag::default(),
+  };
+
+  let config = yaserde::ser::Config {
+    write_document_declaration: false,
+    self_closing: false,
+    ..Default::default()
+  };
+
+  assert_eq!(
+    yaserde::ser::to_string_with_config(&model, &config).unwrap(),
+    "<base><flag /><other></other></base>"
+  );
+}
+
+#[test]
+fn empty_element_maybe_string() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "base")]
+  pub struct XmlStruct {
+    first: MaybeString,
+    second: MaybeString,
+    #[yaserde(empty = "explicit")]
+    third: MaybeString,
+  }
+
+  // The parsed form is kept, a new value follows the field
+  let mut model: XmlStruct =
+    yaserde::de::from_str("<base><first></first><second/><third/></base>").unwrap();
+  assert_eq!(model.first.empty_element, Some(EmptyElement::Explicit));
+  assert_eq!(model.second.empty_element, Some(EmptyElement::SelfClosing));
+  model.third = MaybeString::from(None);
+
+  let content = "<base><first></first><second /><third></third></base>";
+  serialize_and_validate!(model, content);
+}
+
+#[test]
+#[cfg(feature = "quick-xml")]
+fn empty_element_quick_xml() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize)]
+  #[yaserde(rename = "base")]
+  pub struct XmlStruct {
+    first: MaybeString,
+    second: MaybeString,
+    raw: yaserde::RawXml,
+  }
+
+  let model: XmlStruct = yaserde::de::from_str_with_parser(
+    "<base><first></first><second/><raw><a/><b></b></raw></base>",
+    yaserde::de::Parser::QuickXml,
+  )
+  .unwrap();
+
+  assert_eq!(model.first.empty_element, Some(EmptyElement::Explicit));
+  assert_eq!(model.second.empty_element, Some(EmptyElement::SelfClosing));
+  assert_eq!(model.raw.0, "<raw><a/><b></b></raw>");
+}
@@ -0,0 +1,143 @@
+#![cfg(feature = "encoding_rs")]
+
+#[macro_use]
+extern crate yaserde_derive;
+
+use encoding_rs::{SHIFT_JIS, UTF_16BE, UTF_16LE, WINDOWS_1252};
+use yaserde::ser::{to_writer_with_config, Config};
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+#[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+#[yaserde(rename = "book")]
+pub struct Book {
+  #[yaserde(attribute = true)]
+  lang: String,
+  title: String,
+}
+
+fn book(lang: &str, title: &str) -> Book {
+  Book {
+    lang: lang.to_string(),
+    title: title.to_string(),
+  }
+}
+
+fn to_bytes(model: &Book, config: &Config) -> Vec<u8> {
+  let mut buf = vec![];
+  to_writer_with_config(model, &mut buf, config).unwrap();
+  buf
+}
+
+#[test]
+fn ser_single_byte_encoding() {
+  init();
+
+  let config = Config {
+    encoding: WINDOWS_1252,
+    ..Default::default()
+  };
+
+  // Unmappable characters are written as character references
+  assert_eq!(
+    to_bytes(&book("fr", "Café → thé"), &config),
+    b"<?xml version=\"1.0\" encoding=\"windows-1252\"?><book lang=\"fr\"><title>Caf\xe9 &#8594; th\xe9</title></book>"
+  );
+
+  // The declaration can name the encoding differently
+  let config = Config {
+    encoding: WINDOWS_1252,
+    declaration: Some(yaserde::events::Declaration {
+      encoding: Some("ISO-8859-1".to_string()),
+      ..Default::default()
+    }),
+    ..Default::default()
+  };
+
+  assert_eq!(
+    to_bytes(&book("fr", "Café"), &config),
+    b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?><book lang=\"fr\"><title>Caf\xe9</title></book>"
+  );
+}
+
+#[test]
+fn ser_utf16() {
+  init();
+
+  let model = book("ja", "吾輩は猫である");
+  let expected = r#"<?xml version="1.0" encoding="UTF-16LE"?><book lang="ja"><title>吾輩は猫である</title></book>"#;
+
+  let config = Config {
+    encoding: UTF_16LE,
+    ..Default::default()
+  };
+  let content = to_bytes(&model, &config);
+
+  let mut units = vec![0xfeff];
+  units.extend(expected.encode_utf16());
+  let expected_bytes: Vec<u8> = units.iter().flat_map(|unit| unit.to_le_bytes()).collect();
+  assert_eq!(content, expected_bytes);
+
+  let loaded: Book = yaserde::de::from_reader(content.as_slice()).unwrap();
+  assert_eq!(loaded, model);
+
+  let config = Config {
+    encoding: UTF_16BE,
+    ..Default::default()
+  };
+  let content = to_bytes(&model, &config);
+
+  assert_eq!(&content[..4], &[0xfe, 0xff, 0, b'<']);
+  let loaded: Book = yaserde::de::from_reader(content.as_slice()).unwrap();
+  assert_eq!(loaded, model);
+}
+
+#[test]
+fn ser_to_string_only_utf8() {
+  init();
+
+  let config = Config {
+    encoding: WINDOWS_1252,
+    ..Default::default()
+  };
+
+  assert_eq!(
+    yaserde::ser::to_string_with_config(&book("fr", "Café"), &config),
+    Err("unable to write windows-1252 in a String, use to_writer_with_config".to_string())
+  );
+}
+
+#[test]
+fn de_declared_encoding() {
+  init();
+
+  let content = b"<?xml version=\"1.0\" encoding=\"windows-1252\"?><book lang=\"fr\"><title>Caf\xe9 \x80</title></book>";
+  let loaded: Book = yaserde::de::from_reader(&content[..]).unwrap();
+  assert_eq!(loaded, book("fr", "Café €"));
+
+  let (content, _, _) = SHIFT_JIS.encode(
+    r#"<?xml version='1.0' encoding='Shift_JIS'?><book lang="ja"><title>吾輩は猫である</title></book>"#,
+  );
+  let loaded: Book = yaserde::de::from_reader(&content[..]).unwrap();
+  assert_eq!(loaded, book("ja", "吾輩は猫である"));
+
+  #[cfg(feature = "quick-xml")]
+  {
+    let loaded: Book =
+      yaserde::de::from_reader_with_parser(&content[..], yaserde::de::Parser::QuickXml).unwrap();
+    assert_eq!(loaded, book("ja", "吾輩は猫である"));
+  }
+}
+
+#[test]
+fn de_string_ignores_declared_encoding() {
+  init();
+
+  // A string is already decoded
+  let content =
+    r#"<?xml version="1.0" encoding="ISO-8859-1"?><book lang="fr"><title>Café</title></book>"#;
+  let loaded: Book = yaserde::de::from_str(content).unwrap();
+  assert_eq!(loaded, book("fr", "Café"));
+}
@@ -44,3 +44,33 @@ fn de_wrong_end_balise() {
     Err("Unexpected closing tag: book != author".to_owned())
   );
 }
+
+#[test]
+fn ser_writer_error() {
+  init();
+
+  #[derive(YaSerialize, PartialEq, Debug)]
+  #[yaserde(rename = "book")]
+  pub struct Book {
+    author: String,
+  }
+
+  struct FailingWriter;
+
+  impl std::io::Write for FailingWriter {
+    fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+      Err(std::io::Error::other("disk full"))
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+      Ok(())
+    }
+  }
+
+  let model = Book {
+    author: "Antoine de Saint-Exupéry".to_string(),
+  };
+
+  let result = yaserde::ser::to_writer(&model, FailingWriter);
+  assert!(result.unwrap_err().contains("disk full"));
+}
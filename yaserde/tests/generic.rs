@@ -1,7 +1,7 @@
 #[macro_use]
 extern crate yaserde;
 
-use yaserde::{YaDeserialize, YaSerialize};
+use yaserde::{YaDeserialize, YaDeserializeOwned, YaSerialize};
 
 fn init() {
   let _ = env_logger::builder().is_test(true).try_init();
@@ -15,7 +15,7 @@ fn generic() {
   #[yaserde(rename = "base")]
   pub struct Base<G>
   where
-    G: YaSerialize + YaDeserialize + Default,
+    G: YaSerialize + YaDeserializeOwned + Default,
   {
     background: G,
   }
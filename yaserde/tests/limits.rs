@@ -0,0 +1,167 @@
+use std::io::Read;
+use yaserde::de::{from_reader_with_config, from_str_with_config, Config, Error, Limit, Parser};
+use yaserde::RawXml;
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn parsers() -> Vec<Parser> {
+  vec![
+    Parser::XmlRs,
+    #[cfg(feature = "quick-xml")]
+    Parser::QuickXml,
+  ]
+}
+
+fn limited(parser: Parser) -> Config {
+  Config {
+    parser,
+    max_depth: Some(64),
+    max_events: Some(10_000),
+    max_text_size: Some(65_536),
+    max_attributes: Some(100),
+    ..Default::default()
+  }
+}
+
+#[test]
+fn within_limits() {
+  init();
+
+  let content = format!(
+    r#"<doc a="1">{}<text>{}</text></doc>"#,
+    "<b/>".repeat(1_000),
+    "x".repeat(65_536)
+  );
+
+  for parser in parsers() {
+    let loaded: Result<RawXml, Error> = from_str_with_config(&content, &limited(parser));
+    assert!(loaded.is_ok(), "{:?}", parser);
+  }
+}
+
+#[test]
+fn deep_nesting() {
+  init();
+
+  // Never closed, the limit is reached first
+  let content = "<a>".repeat(100_000);
+
+  for parser in parsers() {
+    let loaded: Result<RawXml, Error> = from_str_with_config(&content, &limited(parser));
+    assert_eq!(loaded, Err(Error::LimitExceeded(Limit::Depth(64))));
+
+    let loaded: Result<RawXml, Error> =
+      from_reader_with_config(content.as_bytes(), &limited(parser));
+    assert_eq!(loaded, Err(Error::LimitExceeded(Limit::Depth(64))));
+  }
+}
+
+#[test]
+fn many_events() {
+  init();
+
+  let content = format!("<doc>{}</doc>", "<b/><!-- c -->".repeat(100_000));
+
+  for parser in parsers() {
+    let loaded: Result<RawXml, Error> = from_str_with_config(&content, &limited(parser));
+    assert_eq!(loaded, Err(Error::LimitExceeded(Limit::Events(10_000))));
+  }
+}
+
+#[test]
+fn large_text() {
+  init();
+
+  let content = format!("<doc><![CDATA[{}]]></doc>", "x".repeat(1_000_000));
+
+  for parser in parsers() {
+    let loaded: Result<RawXml, Error> = from_str_with_config(&content, &limited(parser));
+    assert_eq!(loaded, Err(Error::LimitExceeded(Limit::TextSize(65_536))));
+  }
+}
+
+/// A document starting with `start`, then text that never ends
+struct EndlessText {
+  start: &'static [u8],
+  /// Bytes read so far
+  read: usize,
+}
+
+impl Read for EndlessText {
+  fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+    let start = self.start.get(self.read..).unwrap_or_default();
+    let length = buf.len().min(start.len().max(1_024));
+    for (index, byte) in buf[..length].iter_mut().enumerate() {
+      *byte = *start.get(index).unwrap_or(&b'x');
+    }
+    self.read += length;
+    Ok(length)
+  }
+}
+
+#[test]
+fn endless_text() {
+  init();
+
+  for start in [
+    &b"<doc>"[..],
+    b"<doc>&lt;",
+    b"<doc><![CDATA[",
+    b"<doc>x<![CDATA[x]]>",
+  ] {
+    for parser in parsers() {
+      let mut reader = EndlessText { start, read: 0 };
+      let loaded: Result<RawXml, Error> = from_reader_with_config(&mut reader, &limited(parser));
+      assert_eq!(loaded, Err(Error::LimitExceeded(Limit::TextSize(65_536))));
+      // Stopped while reading the text, rather than once it is all in memory
+      assert!(
+        reader.read < 65_536 + 16_384,
+        "{:?} read {}",
+        parser,
+        reader.read
+      );
+    }
+  }
+}
+
+#[test]
+fn merged_text() {
+  init();
+
+  let content = format!("<doc>{}</doc>", "x<![CDATA[x]]>".repeat(100_000));
+
+  for parser in parsers() {
+    let loaded: Result<RawXml, Error> = from_str_with_config(&content, &limited(parser));
+    assert_eq!(loaded, Err(Error::LimitExceeded(Limit::TextSize(65_536))));
+  }
+}
+
+#[test]
+fn many_attributes() {
+  init();
+
+  let attributes: String = (0..1_000).map(|i| format!(r#" a{}="""#, i)).collect();
+  let content = format!(r#"<doc xmlns:x="urn:x"{}/>"#, attributes);
+
+  for parser in parsers() {
+    let loaded: Result<RawXml, Error> = from_str_with_config(&content, &limited(parser));
+    assert_eq!(loaded, Err(Error::LimitExceeded(Limit::Attributes(100))));
+  }
+}
+
+#[test]
+fn limit_error_message() {
+  init();
+
+  let loaded: Result<RawXml, String> = from_str_with_config(
+    "<a><b/></a>",
+    &Config {
+      max_depth: Some(1),
+      ..Default::default()
+    },
+  )
+  .map_err(String::from);
+  assert_eq!(loaded, Err("Elements are nested deeper than 1".to_string()));
+}
@@ -1,8 +1,5 @@
-#[macro_use]
-extern crate yaserde_derive;
-#[macro_use]
-extern crate yaserde;
 
+use yaserde::ser::EmptyElement;
 use yaserde::MaybeString;
 use yaserde_derive::YaDeserialize;
 use yaserde_derive::YaSerialize;
@@ -23,7 +20,8 @@ fn maybe_string_should_deserialize_empty_element() {
     TestStruct {
       maybe: MaybeString {
         field_name: String::from("maybe"),
-        content: None
+        content: None,
+        empty_element: Some(EmptyElement::SelfClosing),
       }
     }
   );
@@ -40,7 +38,8 @@ fn maybe_string_should_deserialize_content() {
     TestStruct {
       maybe: MaybeString {
         field_name: String::from("maybe"),
-        content: Some(String::from("some content"))
+        content: Some(String::from("some content")),
+        empty_element: None,
       }
     }
   );
@@ -57,7 +56,8 @@ fn maybe_string_should_deserialize_empty_long_format() {
     TestStruct {
       maybe: MaybeString {
         field_name: String::from("maybe"),
-        content: None
+        content: None,
+        empty_element: Some(EmptyElement::Explicit),
       }
     }
   );
@@ -87,3 +87,15 @@ fn maybe_string_should_serialize_content() {
     initial_xml
   );
 }
+
+#[test]
+fn maybe_string_should_serialize_to_empty_long_format() {
+  let initial_xml =
+    r#"<?xml version="1.0" encoding="utf-8"?><TestStruct><maybe></maybe></TestStruct>"#;
+  let test_struct: TestStruct =
+    yaserde::de::from_str(initial_xml).expect("Shoudl deserialize teststruct");
+  assert_eq!(
+    yaserde::ser::to_string(&test_struct).expect("should serialize teststruct"),
+    initial_xml
+  );
+}
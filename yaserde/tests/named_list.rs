@@ -1,16 +1,60 @@
-#[macro_use]
-extern crate yaserde_derive;
-#[macro_use]
-extern crate yaserde;
-
-use yaserde::{RawXml, NamedList};
+use yaserde::{NamedList, RawXml};
 
 #[test]
 fn deserialize_namedlist() {
-  let mut expected_elements: Vec<(String, RawXml)> = Vec::new();
-  expected_elements.push((String::from("struct1"), RawXml::default()));
+  // The items are the children of the root element
+  let expected_elements: Vec<(String, RawXml)> =
+    vec![(String::from("foo"), RawXml(String::from("<foo>foo</foo>")))];
 
   let deserialized: NamedList<RawXml> =
     yaserde::de::from_str("<struct1><foo>foo</foo></struct1>").unwrap();
   assert_eq!(expected_elements, deserialized.elements);
 }
+
+#[test]
+fn deserialize_namedlist_with_config() {
+  use yaserde::de::{from_str_with_config, Config};
+
+  let content = "<list>\n  <!-- first -->\n  <a><![CDATA[1]]></a>\n  <b>2</b>\n</list>";
+  let configs = [
+    Config {
+      trim_whitespace: false,
+      ..Default::default()
+    },
+    Config {
+      cdata_to_characters: false,
+      ..Default::default()
+    },
+    Config {
+      comments: true,
+      ..Default::default()
+    },
+    Config {
+      trim_whitespace: false,
+      cdata_to_characters: false,
+      comments: true,
+      ..Default::default()
+    },
+  ];
+
+  for config in &configs {
+    let deserialized: NamedList<RawXml> = from_str_with_config(content, config).unwrap();
+    let names: Vec<&str> = deserialized
+      .elements
+      .iter()
+      .map(|(name, _)| name.as_str())
+      .collect();
+    assert_eq!(names, ["a", "b"], "{:?}", config);
+  }
+
+  let config = Config {
+    trim_whitespace: false,
+    ..Default::default()
+  };
+  let deserialized: Result<NamedList<RawXml>, String> =
+    from_str_with_config("<list>text<a/></list>", &config).map_err(String::from);
+  assert_eq!(
+    deserialized.map(|list| list.elements.len()),
+    Err("Unexpected text in NamedList: text".to_string())
+  );
+}
@@ -290,8 +290,7 @@ fn struct_implicit_default_namespace() {
 
   let content = r#"<tt xmlns="http://www.w3.org/ns/ttml" xmlns:ttm="http://www.w3.org/ns/ttml#metadata"><item>something</item></tt>"#;
   serialize_and_validate!(model, content);
-  // TODO
-  // deserialize_and_validate!(content, model, XmlStruct);
+  deserialize_and_validate!(content, model, XmlStruct);
 }
 
 #[test]
@@ -317,8 +316,7 @@ fn struct_explicit_default_namespace() {
 
   let content = r#"<tt xmlns="http://www.w3.org/ns/ttml" xmlns:ttm="http://www.w3.org/ns/ttml#metadata"><item>something</item></tt>"#;
   serialize_and_validate!(model, content);
-  // TODO
-  // deserialize_and_validate!(content, model, XmlStruct);
+  deserialize_and_validate!(content, model, XmlStruct);
 }
 
 #[test]
@@ -415,8 +413,7 @@ fn enum_multi_namespaces() {
     </root>
   "#;
   serialize_and_validate!(model, content);
-  // TODO
-  // deserialize_and_validate!(content, model, XmlStruct);
+  deserialize_and_validate!(content, model, XmlStruct);
 }
 
 #[test]
@@ -493,6 +490,75 @@ fn struct_bad_namespace() {
   );
 }
 
+#[test]
+fn struct_namespace_aliases() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(
+    rename = "Envelope",
+    prefix = "soap",
+    namespaces = {
+      "soap" = "http://schemas.xmlsoap.org/soap/envelope/"
+    }
+  )]
+  pub struct Envelope {
+    #[yaserde(attribute = true, prefix = "soap", rename = "encodingStyle")]
+    encoding_style: String,
+    #[yaserde(prefix = "soap", rename = "Body")]
+    body: String,
+  }
+
+  let model = Envelope {
+    encoding_style: "rpc".to_string(),
+    body: "ping".to_string(),
+  };
+
+  let content = r#"<soap:Envelope xmlns:soap="http://www.w3.org/2003/05/soap-envelope" soap:encodingStyle="rpc"><soap:Body>ping</soap:Body></soap:Envelope>"#;
+
+  let loaded: Result<Envelope, String> = yaserde::de::from_str(content);
+  assert_eq!(
+    loaded,
+    Err("bad namespace for Envelope, found http://www.w3.org/2003/05/soap-envelope".to_string())
+  );
+
+  let config = yaserde::de::Config {
+    namespace_aliases: [(
+      "http://www.w3.org/2003/05/soap-envelope".to_string(),
+      "http://schemas.xmlsoap.org/soap/envelope/".to_string(),
+    )]
+    .into(),
+    ..Default::default()
+  };
+  let loaded: Envelope = yaserde::de::from_str_with_config(content, &config).unwrap();
+  assert_eq!(loaded, model);
+
+  // SOAP 1.1 documents are still read
+  let loaded: Envelope = yaserde::de::from_str_with_config(
+    &content.replace(
+      "http://www.w3.org/2003/05/soap-envelope",
+      "http://schemas.xmlsoap.org/soap/envelope/",
+    ),
+    &config,
+  )
+  .unwrap();
+  assert_eq!(loaded, model);
+
+  let config = yaserde::ser::Config {
+    write_document_declaration: false,
+    namespace_aliases: [(
+      "http://schemas.xmlsoap.org/soap/envelope/".to_string(),
+      "http://www.w3.org/2003/05/soap-envelope".to_string(),
+    )]
+    .into(),
+    ..Default::default()
+  };
+  assert_eq!(
+    yaserde::ser::to_string_with_config(&model, &config).unwrap(),
+    content
+  );
+}
+
 #[test]
 fn struct_default_namespace_no_prefix() {
   init();
@@ -523,3 +589,243 @@ fn struct_default_namespace_no_prefix() {
   serialize_and_validate!(model, content);
   deserialize_and_validate!(content, model, Book);
 }
+
+#[test]
+fn struct_attributes_with_same_local_name() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "item", namespaces = {
+    "ns1" = "http://www.sample.com/ns/domain1",
+    "ns2" = "http://www.sample.com/ns/domain2"
+  })]
+  pub struct Item {
+    #[yaserde(attribute = true)]
+    id: String,
+    #[yaserde(attribute = true, prefix = "ns1", rename = "id")]
+    ns1_id: String,
+    #[yaserde(attribute = true, prefix = "ns2", rename = "id")]
+    ns2_id: Option<String>,
+  }
+
+  let model = Item {
+    id: "0".to_string(),
+    ns1_id: "1".to_string(),
+    ns2_id: None,
+  };
+
+  let content = r#"<item xmlns:ns1="http://www.sample.com/ns/domain1" xmlns:ns2="http://www.sample.com/ns/domain2" id="0" ns1:id="1" />"#;
+  serialize_and_validate!(model, content);
+
+  // The prefix bound to a namespace doesn't matter
+  let content = r#"<item xmlns:a="http://www.sample.com/ns/domain1" xmlns:b="http://www.sample.com/ns/other" a:id="1" b:id="2" id="0" />"#;
+  #[cfg(feature = "quick-xml")]
+  {
+    let loaded: Item =
+      yaserde::de::from_str_with_parser(content, yaserde::de::Parser::QuickXml).unwrap();
+    assert_eq!(loaded, model);
+  }
+
+  deserialize_and_validate!(content, model, Item);
+}
+
+#[test]
+fn struct_unqualified_attributes() {
+  init();
+
+  // Unprefixed attributes are in no namespace, even with a default namespace
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "item", default_namespace = "ns", namespaces = {
+    "ns" = "http://www.sample.com/ns/domain",
+  })]
+  pub struct Item {
+    #[yaserde(attribute = true)]
+    id: Option<String>,
+    name: String,
+  }
+
+  let content = r#"<item xmlns="http://www.sample.com/ns/domain" xmlns:ns="http://www.sample.com/ns/domain" ns:id="1"><name>a</name></item>"#;
+  let loaded: Item = yaserde::de::from_str(content).unwrap();
+  assert_eq!(loaded.id, None);
+
+  let content = r#"<item xmlns="http://www.sample.com/ns/domain" id="1"><name>a</name></item>"#;
+  let loaded: Item = yaserde::de::from_str(content).unwrap();
+  assert_eq!(loaded.id.as_deref(), Some("1"));
+  assert_eq!(loaded.name, "a");
+}
+
+#[test]
+fn enum_tag_namespace() {
+  init();
+
+  #[derive(Debug, Default, PartialEq, YaDeserialize)]
+  #[yaserde(rename = "shape", tag = "xsi:type", namespaces = {
+    "xsi" = "http://www.w3.org/2001/XMLSchema-instance",
+    "ns" = "http://www.sample.com/ns/shapes"
+  })]
+  pub enum Shape {
+    #[default]
+    Unknown,
+    #[yaserde(prefix = "ns")]
+    Circle,
+    #[yaserde(prefix = "ns")]
+    Square,
+  }
+
+  let content = r#"<shape xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:s="http://www.sample.com/ns/shapes" xsi:type="s:Square" />"#;
+  let loaded: Shape = yaserde::de::from_str(content).unwrap();
+  assert_eq!(loaded, Shape::Square);
+
+  // In another namespace
+  let content = r#"<shape xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:s="http://www.sample.com/ns/other" xsi:type="s:Square" />"#;
+  let loaded: Shape = yaserde::de::from_str(content).unwrap();
+  assert_eq!(loaded, Shape::Unknown);
+
+  // The tag itself is namespaced
+  let content = r#"<shape xmlns:s="http://www.sample.com/ns/shapes" type="s:Square" />"#;
+  let loaded: Result<Shape, String> = yaserde::de::from_str(content);
+  assert!(loaded.is_err());
+}
+
+#[test]
+fn enum_value_namespace() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize)]
+  #[yaserde(rename = "root", namespaces = {
+    "ns1" = "http://www.sample.com/ns/domain1",
+    "ns2" = "http://www.sample.com/ns/domain2"
+  })]
+  #[derive(Default)]
+  pub enum XmlEnum {
+    #[default]
+    #[yaserde(prefix = "ns1")]
+    Item1,
+    #[yaserde(prefix = "ns2")]
+    Item2,
+  }
+
+  // Prefixes in values are resolved with the namespaces in scope
+  let content = r#"<root xmlns:a="http://www.sample.com/ns/domain2">a:Item2</root>"#;
+  let loaded: XmlEnum = yaserde::de::from_str(content).unwrap();
+  assert_eq!(loaded, XmlEnum::Item2);
+
+  let content = r#"<root xmlns:a="http://www.sample.com/ns/domain1">a:Item2</root>"#;
+  let loaded: XmlEnum = yaserde::de::from_str(content).unwrap();
+  assert_eq!(loaded, XmlEnum::Item1);
+
+  // Unprefixed values match by name
+  let content = r#"<root>Item2</root>"#;
+  let loaded: XmlEnum = yaserde::de::from_str(content).unwrap();
+  assert_eq!(loaded, XmlEnum::Item2);
+}
+
+#[test]
+fn struct_hoisted_namespaces() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "library")]
+  pub struct Library {
+    #[yaserde(rename = "book", prefix = "b")]
+    books: Vec<Book>,
+    #[yaserde(rename = "review", prefix = "b")]
+    review: Review,
+  }
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(
+    rename = "book",
+    prefix = "b",
+    namespaces = {
+      "b" = "http://www.sample.com/ns/book",
+      "p" = "http://www.sample.com/ns/person"
+    }
+  )]
+  pub struct Book {
+    #[yaserde(prefix = "p")]
+    author: Author,
+  }
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(
+    rename = "author",
+    prefix = "p",
+    namespaces = {
+      "p" = "http://www.sample.com/ns/person"
+    }
+  )]
+  pub struct Author {
+    #[yaserde(prefix = "p")]
+    name: String,
+  }
+
+  // Another URI bound to `b`
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(
+    rename = "review",
+    prefix = "b",
+    namespaces = {
+      "b" = "http://www.sample.com/ns/review"
+    }
+  )]
+  pub struct Review {
+    #[yaserde(text = true)]
+    content: String,
+  }
+
+  let book = |name: &str| Book {
+    author: Author {
+      name: name.to_string(),
+    },
+  };
+  let model = Library {
+    books: vec![book("Saint-Exupéry"), book("Herbert")],
+    review: Review {
+      content: "Good".to_string(),
+    },
+  };
+
+  // Without hoisting, each book declares the namespaces it uses
+  let content = yaserde::ser::to_string_with_config(
+    &model,
+    &yaserde::ser::Config {
+      write_document_declaration: false,
+      ..Default::default()
+    },
+  )
+  .unwrap();
+  assert_eq!(
+    content,
+    concat!(
+      r#"<library>"#,
+      r#"<b:book xmlns:b="http://www.sample.com/ns/book" xmlns:p="http://www.sample.com/ns/person"><p:author><p:name>Saint-Exupéry</p:name></p:author></b:book>"#,
+      r#"<b:book xmlns:b="http://www.sample.com/ns/book" xmlns:p="http://www.sample.com/ns/person"><p:author><p:name>Herbert</p:name></p:author></b:book>"#,
+      r#"<b:review xmlns:b="http://www.sample.com/ns/review">Good</b:review>"#,
+      r#"</library>"#
+    )
+  );
+
+  let hoisted = yaserde::ser::to_string_with_config(
+    &model,
+    &yaserde::ser::Config {
+      write_document_declaration: false,
+      hoist_namespaces: true,
+      ..Default::default()
+    },
+  )
+  .unwrap();
+  assert_eq!(
+    hoisted,
+    concat!(
+      r#"<library xmlns:p="http://www.sample.com/ns/person">"#,
+      r#"<b:book xmlns:b="http://www.sample.com/ns/book"><p:author><p:name>Saint-Exupéry</p:name></p:author></b:book>"#,
+      r#"<b:book xmlns:b="http://www.sample.com/ns/book"><p:author><p:name>Herbert</p:name></p:author></b:book>"#,
+      r#"<b:review xmlns:b="http://www.sample.com/ns/review">Good</b:review>"#,
+      r#"</library>"#
+    )
+  );
+
+  let loaded: Library = yaserde::de::from_str(&hoisted).unwrap();
+  assert_eq!(loaded, model);
+}
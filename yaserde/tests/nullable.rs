@@ -0,0 +1,80 @@
+#[macro_use]
+extern crate yaserde;
+#[macro_use]
+extern crate yaserde_derive;
+
+use yaserde::Nullable;
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+#[test]
+fn nullable_simple_types() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "interface")]
+  pub struct Interface {
+    descr: Nullable<String>,
+    mtu: Nullable<u32>,
+    gateway: Nullable<String>,
+  }
+
+  let model = Interface {
+    descr: Nullable::Empty,
+    mtu: Nullable::Value(1500),
+    gateway: Nullable::Absent,
+  };
+
+  let content = "<interface><descr /><mtu>1500</mtu></interface>";
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Interface);
+
+  let model = Interface {
+    descr: Nullable::Value("WAN".to_string()),
+    mtu: Nullable::Empty,
+    gateway: Nullable::Empty,
+  };
+
+  deserialize_and_validate!(
+    "<interface><gateway></gateway><descr>WAN</descr><mtu /></interface>",
+    model,
+    Interface
+  );
+}
+
+#[test]
+fn nullable_struct() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "system")]
+  pub struct System {
+    dns: Nullable<Dns>,
+    ntp: Nullable<Dns>,
+    syslog: Nullable<Dns>,
+  }
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  pub struct Dns {
+    #[yaserde(attribute = true)]
+    port: u16,
+    server: String,
+  }
+
+  let model = System {
+    dns: Nullable::Value(Dns {
+      port: 53,
+      server: "1.1.1.1".to_string(),
+    }),
+    ntp: Nullable::Empty,
+    syslog: Nullable::Absent,
+  };
+
+  let content = r#"<system><dns port="53"><server>1.1.1.1</server></dns><ntp /></system>"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, System);
+}
@@ -1,7 +1,5 @@
 #[macro_use]
 extern crate yaserde;
-#[macro_use]
-extern crate yaserde_derive;
 
 use yaserde_derive::{YaDeserialize, YaSerialize};
 
@@ -0,0 +1,129 @@
+#[macro_use]
+extern crate yaserde_derive;
+
+use std::collections::BTreeMap;
+use yaserde::de::Deserializer;
+use yaserde::ser::{to_string_with_config, Config};
+use yaserde::YaDeserialize;
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+#[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+#[yaserde(rename = "Envelope", prefix = "s", namespaces = {
+  "s" = "http://schemas.xmlsoap.org/soap/envelope/",
+  "m" = "urn:example:prices"
+})]
+pub struct Envelope {
+  #[yaserde(attribute = true, prefix = "s", rename = "encodingStyle")]
+  encoding_style: String,
+  #[yaserde(prefix = "s", rename = "Body")]
+  body: Body,
+}
+
+#[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+#[yaserde(rename = "Body", prefix = "s", namespaces = {
+  "s" = "http://schemas.xmlsoap.org/soap/envelope/",
+  "m" = "urn:example:prices"
+})]
+pub struct Body {
+  #[yaserde(prefix = "m", rename = "Price")]
+  price: u32,
+  note: String,
+}
+
+fn model() -> Envelope {
+  Envelope {
+    encoding_style: "soap".to_string(),
+    body: Body {
+      price: 34,
+      note: "a".to_string(),
+    },
+  }
+}
+
+fn config(prefixes: &[(&str, &str)]) -> Config {
+  Config {
+    write_document_declaration: false,
+    prefixes: prefixes
+      .iter()
+      .map(|(uri, prefix)| (uri.to_string(), prefix.to_string()))
+      .collect(),
+    ..Default::default()
+  }
+}
+
+#[test]
+fn default_prefixes() {
+  init();
+
+  assert_eq!(
+    to_string_with_config(&model(), &config(&[])).unwrap(),
+    r#"<s:Envelope xmlns:m="urn:example:prices" xmlns:s="http://schemas.xmlsoap.org/soap/envelope/" s:encodingStyle="soap"><s:Body><m:Price>34</m:Price><note>a</note></s:Body></s:Envelope>"#
+  );
+}
+
+#[test]
+fn runtime_prefixes() {
+  init();
+
+  let prefixes = [
+    ("http://schemas.xmlsoap.org/soap/envelope/", "soap"),
+    ("urn:example:prices", "p"),
+  ];
+
+  assert_eq!(
+    to_string_with_config(&model(), &config(&prefixes)).unwrap(),
+    r#"<soap:Envelope xmlns:p="urn:example:prices" xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/" soap:encodingStyle="soap"><soap:Body><p:Price>34</p:Price><note>a</note></soap:Body></soap:Envelope>"#
+  );
+}
+
+#[test]
+fn runtime_default_namespace() {
+  init();
+
+  let prefixes = [("http://schemas.xmlsoap.org/soap/envelope/", "")];
+
+  // Attributes keep a prefix, unprefixed elements stay in no namespace
+  assert_eq!(
+    to_string_with_config(&model(), &config(&prefixes)).unwrap(),
+    r#"<Envelope xmlns="http://schemas.xmlsoap.org/soap/envelope/" xmlns:m="urn:example:prices" xmlns:s="http://schemas.xmlsoap.org/soap/envelope/" s:encodingStyle="soap"><Body><m:Price>34</m:Price><note xmlns="">a</note></Body></Envelope>"#
+  );
+}
+
+#[test]
+fn round_trip_prefixes() {
+  init();
+
+  let content = r#"<ns0:Envelope xmlns:ns0="http://schemas.xmlsoap.org/soap/envelope/" xmlns:ns1="urn:example:prices" ns0:encodingStyle="soap"><ns0:Body><ns1:Price>34</ns1:Price><note>a</note></ns0:Body></ns0:Envelope>"#;
+
+  let mut deserializer = Deserializer::new_from_reader(content.as_bytes());
+  let loaded = Envelope::deserialize(&mut deserializer).unwrap();
+  assert_eq!(loaded, model());
+
+  let prefixes: BTreeMap<String, String> = deserializer.prefixes().clone();
+  assert_eq!(
+    prefixes,
+    BTreeMap::from([
+      (
+        "http://schemas.xmlsoap.org/soap/envelope/".to_string(),
+        "ns0".to_string()
+      ),
+      ("urn:example:prices".to_string(), "ns1".to_string()),
+    ])
+  );
+
+  let config = Config {
+    write_document_declaration: false,
+    prefixes,
+    ..Default::default()
+  };
+  let written = to_string_with_config(&loaded, &config).unwrap();
+  assert!(written.starts_with(
+    r#"<ns0:Envelope xmlns:ns0="http://schemas.xmlsoap.org/soap/envelope/" xmlns:ns1="urn:example:prices" ns0:encodingStyle="soap">"#
+  ));
+
+  let reloaded: Envelope = yaserde::de::from_str(&written).unwrap();
+  assert_eq!(reloaded, model());
+}
@@ -0,0 +1,87 @@
+#[macro_use]
+extern crate yaserde;
+#[macro_use]
+extern crate yaserde_derive;
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+// Presence fields must be `bool`, an `Option<bool>` is rejected by the derive
+#[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+#[yaserde(rename = "rule")]
+pub struct Rule {
+  #[yaserde(attribute = true)]
+  name: String,
+  #[yaserde(presence)]
+  disabled: bool,
+  #[yaserde(presence, rename = "log")]
+  logging: bool,
+  quick: bool,
+}
+
+#[test]
+fn presence_element() {
+  init();
+
+  let model = Rule {
+    name: "ssh".to_string(),
+    disabled: true,
+    logging: false,
+    quick: false,
+  };
+
+  let content = r#"<rule name="ssh"><disabled /><quick>false</quick></rule>"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Rule);
+
+  let model = Rule {
+    name: "ssh".to_string(),
+    disabled: true,
+    logging: true,
+    quick: true,
+  };
+
+  let content = r#"
+    <rule name="ssh">
+      <log></log>
+      <quick>true</quick>
+      <disabled>1</disabled>
+    </rule>"#;
+
+  deserialize_and_validate!(content, model, Rule);
+}
+
+#[test]
+fn presence_absent_and_empty() {
+  init();
+
+  let model = Rule {
+    name: "ssh".to_string(),
+    disabled: false,
+    logging: false,
+    quick: true,
+  };
+
+  let content = r#"<rule name="ssh"><quick>true</quick></rule>"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Rule);
+
+  let contents = [
+    r#"<rule name="ssh"><disabled/><log/><quick>true</quick></rule>"#,
+    r#"<rule name="ssh"><disabled></disabled><log></log><quick>true</quick></rule>"#,
+  ];
+
+  for content in contents {
+    let model = Rule {
+      name: "ssh".to_string(),
+      disabled: true,
+      logging: true,
+      quick: true,
+    };
+
+    deserialize_and_validate!(content, model, Rule);
+  }
+}
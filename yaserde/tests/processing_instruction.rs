@@ -0,0 +1,125 @@
+#[macro_use]
+extern crate yaserde;
+#[macro_use]
+extern crate yaserde_derive;
+
+use yaserde::events::Prolog;
+use yaserde::ProcessingInstruction;
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+#[derive(Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[yaserde(rename = "note")]
+pub struct Note {
+  to: String,
+}
+
+#[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+#[yaserde(rename = "document")]
+pub struct Document {
+  instructions: Vec<ProcessingInstruction>,
+  title: String,
+  note: Note,
+}
+
+#[test]
+fn ser_config_prolog() {
+  init();
+
+  let model = Note {
+    to: "Tove".to_string(),
+  };
+
+  let config = yaserde::ser::Config {
+    prolog: vec![
+      Prolog::ProcessingInstruction(ProcessingInstruction::new(
+        "xml-stylesheet",
+        Some(r#"type="text/xsl" href="note.xsl""#),
+      )),
+      Prolog::DocType(r#"note SYSTEM "note.dtd""#.to_string()),
+    ],
+    ..Default::default()
+  };
+
+  assert_eq!(
+    yaserde::ser::to_string_with_config(&model, &config).unwrap(),
+    r#"<?xml version="1.0" encoding="utf-8"?><?xml-stylesheet type="text/xsl" href="note.xsl"?><!DOCTYPE note SYSTEM "note.dtd"><note><to>Tove</to></note>"#
+  );
+
+  let config = yaserde::ser::Config {
+    perform_indent: true,
+    write_document_declaration: false,
+    prolog: vec![Prolog::DocType("note".to_string())],
+    ..Default::default()
+  };
+
+  assert_eq!(
+    yaserde::ser::to_string_with_config(&model, &config).unwrap(),
+    "<!DOCTYPE note>\n<note>\n  <to>Tove</to>\n</note>"
+  );
+}
+
+#[test]
+fn processing_instruction_fields() {
+  init();
+
+  let content = r#"<document><?target first data?><title>Notes</title><?empty?><note><?skipped?><to>Tove</to></note></document>"#;
+
+  let model = Document {
+    instructions: vec![
+      ProcessingInstruction::new("target", Some("first data")),
+      ProcessingInstruction::new("empty", None),
+    ],
+    title: "Notes".to_string(),
+    note: Note {
+      to: "Tove".to_string(),
+    },
+  };
+
+  // Written back at the position of the field
+  serialize_and_validate!(
+    model,
+    r#"<document><?target first data?><?empty?><title>Notes</title><note><to>Tove</to></note></document>"#
+  );
+  deserialize_and_validate!(content, model, Document);
+}
+
+#[test]
+#[cfg(feature = "quick-xml")]
+fn processing_instruction_fields_quick_xml() {
+  init();
+
+  let content = r#"<document><?target first data?><title>Notes</title><note><?skipped?><to>Tove</to></note></document>"#;
+
+  let loaded: Document =
+    yaserde::de::from_str_with_parser(content, yaserde::de::Parser::QuickXml).unwrap();
+
+  assert_eq!(
+    loaded.instructions,
+    vec![ProcessingInstruction::new("target", Some("first data"))]
+  );
+}
+
+#[test]
+fn de_prolog() {
+  init();
+
+  let content = r#"<?xml version="1.0"?>
+    <?xml-stylesheet href="note.xsl"?>
+    <!DOCTYPE note>
+    <note><?inside?><to>Tove</to></note>"#;
+
+  let mut deserializer = yaserde::de::Deserializer::new_from_reader(content.as_bytes());
+  let loaded = <Note as yaserde::YaDeserialize>::deserialize(&mut deserializer).unwrap();
+
+  assert_eq!(loaded.to, "Tove");
+  assert_eq!(
+    deserializer.prolog(),
+    &[Prolog::ProcessingInstruction(ProcessingInstruction::new(
+      "xml-stylesheet",
+      Some(r#"href="note.xsl""#)
+    ))]
+  );
+}
@@ -0,0 +1,182 @@
+#![cfg(feature = "quick-xml")]
+
+#[macro_use]
+extern crate yaserde_derive;
+
+use std::fmt::Debug;
+use yaserde::de::{from_reader_with_parser, from_str_with_parser, Parser};
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn deserialize_with_parsers<T: yaserde::YaDeserializeOwned + Debug + PartialEq>(
+  content: &str,
+  model: T,
+) {
+  let loaded: Result<T, String> = from_reader_with_parser(content.as_bytes(), Parser::XmlRs);
+  assert_eq!(loaded.as_ref(), Ok(&model));
+
+  let loaded: Result<T, String> = from_reader_with_parser(content.as_bytes(), Parser::QuickXml);
+  assert_eq!(loaded.as_ref(), Ok(&model));
+
+  let loaded: Result<T, String> = from_str_with_parser(content, Parser::QuickXml);
+  assert_eq!(loaded, Ok(model));
+}
+
+#[test]
+fn quick_xml_struct() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize)]
+  #[yaserde(rename = "library")]
+  pub struct Library {
+    #[yaserde(attribute = true)]
+    name: String,
+    books: Vec<Book>,
+    #[yaserde(presence)]
+    closed: bool,
+  }
+
+  #[derive(Debug, PartialEq, YaDeserialize)]
+  #[yaserde(rename = "book")]
+  pub struct Book {
+    #[yaserde(attribute = true)]
+    lang: Option<String>,
+    author: String,
+    title: String,
+  }
+
+  let content = r#"<?xml version="1.0" encoding="utf-8"?>
+    <!-- a comment -->
+    <?style sheet?>
+    <library name="Q &amp; A">
+      <books lang="fr">
+        <author>Antoine de Saint-Exupéry</author>
+        <title>Le <![CDATA[Petit]]> Prince</title>
+      </books>
+      <books>
+        <author>J. R. R. Tolkien</author>
+        <title>Bilbo &lt;the hobbit&gt;</title>
+      </books>
+      <closed/>
+    </library>"#;
+
+  let model = Library {
+    name: "Q & A".to_string(),
+    books: vec![
+      Book {
+        lang: Some("fr".to_string()),
+        author: "Antoine de Saint-Exupéry".to_string(),
+        title: "Le Petit Prince".to_string(),
+      },
+      Book {
+        lang: None,
+        author: "J. R. R. Tolkien".to_string(),
+        title: "Bilbo <the hobbit>".to_string(),
+      },
+    ],
+    closed: true,
+  };
+
+  deserialize_with_parsers(content, model);
+}
+
+#[test]
+fn quick_xml_namespaces() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize)]
+  #[yaserde(
+    rename = "book",
+    prefix = "ns",
+    namespaces = {
+      "ns" = "http://www.sample.com/ns/domain",
+      "ns2" = "http://www.sample.com/ns/domain_2"
+    }
+  )]
+  pub struct Book {
+    #[yaserde(prefix = "ns")]
+    author: String,
+    #[yaserde(prefix = "ns2")]
+    title: String,
+  }
+
+  let content = r#"
+    <ns:book xmlns:ns="http://www.sample.com/ns/domain" xmlns:ns2="http://www.sample.com/ns/domain_2">
+      <ns:author>Antoine de Saint-Exupéry</ns:author>
+      <ns2:title>Little prince</ns2:title>
+    </ns:book>"#;
+
+  let model = Book {
+    author: "Antoine de Saint-Exupéry".to_string(),
+    title: "Little prince".to_string(),
+  };
+
+  deserialize_with_parsers(content, model);
+}
+
+#[test]
+fn quick_xml_enum() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize)]
+  #[yaserde(rename = "base")]
+  pub struct Base {
+    color: Color,
+  }
+
+  #[derive(Debug, Default, PartialEq, YaDeserialize)]
+  pub enum Color {
+    #[default]
+    White,
+    Black,
+    Custom(u32),
+  }
+
+  deserialize_with_parsers(
+    "<base><color>Black</color></base>",
+    Base {
+      color: Color::Black,
+    },
+  );
+  deserialize_with_parsers(
+    "<base><color><Custom>42</Custom></color></base>",
+    Base {
+      color: Color::Custom(42),
+    },
+  );
+}
+
+#[test]
+fn quick_xml_errors() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize)]
+  #[yaserde(rename = "book")]
+  pub struct Book {
+    author: String,
+  }
+
+  let loaded: Result<Book, String> =
+    from_reader_with_parser("<book><author>Someone</book>".as_bytes(), Parser::QuickXml);
+  assert!(loaded.is_err());
+
+  let loaded: Result<Book, String> = from_reader_with_parser(
+    "<ns:book><author>Someone</author></ns:book>".as_bytes(),
+    Parser::QuickXml,
+  );
+  assert!(loaded.is_err());
+
+  let loaded: Result<Book, String> = from_reader_with_parser(
+    "<book><author>Someone</author>".as_bytes(),
+    Parser::QuickXml,
+  );
+  assert_eq!(loaded, Err("Unexpected end of stream".to_string()));
+
+  let loaded: Result<Book, String> = from_reader_with_parser("".as_bytes(), Parser::QuickXml);
+  assert_eq!(
+    loaded,
+    Err("Unexpected end of stream: no root element found".to_string())
+  );
+}
@@ -1,4 +1,3 @@
-#[macro_use]
 extern crate yaserde;
 
 use yaserde::RawXml;
@@ -42,7 +41,15 @@ pub struct Child {
 #[test]
 fn rawxml_should_buffer_empty_element() {
   let rawxml: RawXml = yaserde::de::from_str("<something/>").unwrap();
-  assert_eq!(rawxml.0, String::from("<something></something>"));
+  assert_eq!(rawxml.0, String::from("<something/>"));
+}
+
+#[test]
+fn rawxml_should_keep_empty_element_forms() {
+  let xml = r#"<?xml version="1.0" encoding="utf-8"?><xml><a /><b></b><c><d /></c></xml>"#;
+  let rawxml: RawXml = yaserde::de::from_str(xml).unwrap();
+  assert_eq!(rawxml.0, "<xml><a/><b></b><c><d/></c></xml>");
+  assert_eq!(yaserde::ser::to_string(&rawxml).unwrap(), xml);
 }
 
 #[test]
@@ -138,7 +145,7 @@ fn rawxml_should_allow_siblings_after() {
   }
   let xml = r#"<?xml version="1.0" encoding="utf-8"?><Config><raw>allo something</raw><paul>bobob</paul><paul>patate</paul></Config>"#;
   let config: Config = yaserde::de::from_str(xml).unwrap();
-  assert_eq!(config.paul.get(0).unwrap(), "bobob");
+  assert_eq!(config.paul.first().unwrap(), "bobob");
   assert_eq!(config.paul.get(1).unwrap(), "patate");
   assert_eq!(config.paul.len(), 2);
   assert_eq!(config.raw.0, "<raw>allo something</raw>");
@@ -0,0 +1,105 @@
+#[macro_use]
+extern crate yaserde;
+#[macro_use]
+extern crate yaserde_derive;
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+#[test]
+fn separator_attribute() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "rule")]
+  pub struct Rule {
+    #[yaserde(attribute = true, separator = ",")]
+    ports: Vec<u16>,
+    #[yaserde(attribute = true, separator = ";")]
+    hosts: Vec<String>,
+  }
+
+  let model = Rule {
+    ports: vec![22, 80, 443],
+    hosts: vec!["a.example".to_string(), "b example".to_string()],
+  };
+
+  let content = r#"<rule ports="22,80,443" hosts="a.example;b example" />"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Rule);
+
+  let model = Rule {
+    ports: vec![],
+    hosts: vec![],
+  };
+
+  serialize_and_validate!(model, "<rule />");
+  deserialize_and_validate!(r#"<rule ports="" />"#, model, Rule);
+}
+
+#[test]
+fn separator_attribute_trim() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "rule")]
+  pub struct Rule {
+    #[yaserde(attribute = true, separator = ",", trim = true)]
+    ports: Vec<u16>,
+  }
+
+  let model = Rule {
+    ports: vec![22, 80, 443],
+  };
+
+  serialize_and_validate!(model, r#"<rule ports="22,80,443" />"#);
+  deserialize_and_validate!(r#"<rule ports=" 22, 80 ,443, " />"#, model, Rule);
+
+  #[derive(Debug, PartialEq, YaDeserialize)]
+  #[yaserde(rename = "rule")]
+  pub struct UntrimmedRule {
+    #[yaserde(attribute = true, separator = ",")]
+    ports: Vec<u16>,
+  }
+
+  let loaded: Result<UntrimmedRule, String> = yaserde::de::from_str(r#"<rule ports="22, 80" />"#);
+  assert!(loaded.is_err());
+}
+
+#[test]
+fn separator_text() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "ports")]
+  pub struct Ports {
+    #[yaserde(attribute = true)]
+    protocol: String,
+    #[yaserde(text = true, separator = ",", trim = true)]
+    values: Vec<u16>,
+  }
+
+  let model = Ports {
+    protocol: "tcp".to_string(),
+    values: vec![22, 80, 443],
+  };
+
+  serialize_and_validate!(model, r#"<ports protocol="tcp">22,80,443</ports>"#);
+  deserialize_and_validate!(r#"<ports protocol="tcp">22, 80, 443</ports>"#, model, Ports);
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "names")]
+  pub struct Names {
+    #[yaserde(text = true)]
+    values: Vec<String>,
+  }
+
+  let model = Names {
+    values: vec!["first".to_string(), "second".to_string()],
+  };
+
+  serialize_and_validate!(model, "<names>first second</names>");
+  deserialize_and_validate!("<names>first   second</names>", model, Names);
+}
@@ -415,3 +415,144 @@ fn ser_custom() {
   let content = "<Date><Year>2020</Year><Month>1</Month><DoubleDay>10</DoubleDay></Date>";
   serialize_and_validate!(model, content);
 }
+
+#[test]
+fn ser_to_writer() {
+  #[derive(YaSerialize, PartialEq, Debug)]
+  #[yaserde(rename = "base")]
+  pub struct XmlStruct {
+    #[yaserde(attribute = true)]
+    colors: Vec<Color>,
+    items: Vec<String>,
+  }
+
+  #[derive(YaSerialize, PartialEq, Debug)]
+  pub enum Color {
+    Red,
+    Blue,
+  }
+
+  let model = XmlStruct {
+    colors: vec![Color::Red, Color::Blue],
+    items: vec!["é".to_string(), "ü".to_string()],
+  };
+
+  let mut buffer = vec![];
+  yaserde::ser::to_writer(&model, &mut buffer).unwrap();
+
+  assert_eq!(
+    String::from_utf8(buffer).unwrap(),
+    yaserde::ser::to_string(&model).unwrap()
+  );
+  assert_eq!(
+    yaserde::ser::to_string(&model).unwrap(),
+    r#"<?xml version="1.0" encoding="utf-8"?><base colors="Red Blue"><items>é</items><items>ü</items></base>"#
+  );
+}
+
+#[test]
+fn ser_config_formatting() {
+  #[derive(YaSerialize, PartialEq, Debug)]
+  #[yaserde(rename = "base")]
+  pub struct XmlStruct {
+    #[yaserde(attribute = true)]
+    title: String,
+    #[yaserde(text = true)]
+    text: String,
+    empty: Empty,
+  }
+
+  #[derive(YaSerialize, PartialEq, Debug)]
+  pub struct Empty {}
+
+  let model = XmlStruct {
+    title: r#"Say "hi" to 'me'"#.to_string(),
+    text: r#"<"quoted">"#.to_string(),
+    empty: Empty {},
+  };
+
+  let config = yaserde::ser::Config {
+    attribute_quote: yaserde::ser::QuoteStyle::Single,
+    self_closing: false,
+    trailing_newline: true,
+    ..Default::default()
+  };
+
+  assert_eq!(
+    yaserde::ser::to_string_with_config(&model, &config).unwrap(),
+    "<?xml version='1.0' encoding='utf-8'?><base title='Say &quot;hi&quot; to &apos;me&apos;'>&lt;\"quoted\"&gt;\
+     <empty></empty></base>\n"
+  );
+}
+
+#[test]
+fn ser_config_line_ending() {
+  #[derive(YaSerialize, PartialEq, Debug)]
+  #[yaserde(rename = "base")]
+  pub struct XmlStruct {
+    item: String,
+    empty: Empty,
+  }
+
+  #[derive(YaSerialize, PartialEq, Debug)]
+  pub struct Empty {}
+
+  let model = XmlStruct {
+    item: "something".to_string(),
+    empty: Empty {},
+  };
+
+  let config = yaserde::ser::Config {
+    perform_indent: true,
+    write_document_declaration: false,
+    line_ending: yaserde::ser::LineEnding::CrLf,
+    trailing_newline: true,
+    ..Default::default()
+  };
+
+  assert_eq!(
+    yaserde::ser::to_string_with_config(&model, &config).unwrap(),
+    "<base>\r\n  <item>something</item>\r\n  <empty />\r\n</base>\r\n"
+  );
+}
+
+#[test]
+fn ser_config_declaration() {
+  #[derive(YaSerialize, PartialEq, Debug)]
+  #[yaserde(rename = "base")]
+  pub struct XmlStruct {
+    item: String,
+  }
+
+  let model = XmlStruct {
+    item: "something".to_string(),
+  };
+
+  let config = yaserde::ser::Config {
+    declaration: Some(yaserde::events::Declaration {
+      version: yaserde::events::XmlVersion::Version11,
+      encoding: Some("UTF-8".to_string()),
+      standalone: Some(true),
+    }),
+    ..Default::default()
+  };
+
+  assert_eq!(
+    yaserde::ser::to_string_with_config(&model, &config).unwrap(),
+    r#"<?xml version="1.1" encoding="UTF-8" standalone="yes"?><base><item>something</item></base>"#
+  );
+
+  let config = yaserde::ser::Config {
+    perform_indent: true,
+    declaration: Some(yaserde::events::Declaration {
+      encoding: None,
+      ..Default::default()
+    }),
+    ..Default::default()
+  };
+
+  assert_eq!(
+    yaserde::ser::to_string_with_config(&model, &config).unwrap(),
+    "<?xml version=\"1.0\"?>\n<base>\n  <item>something</item>\n</base>"
+  );
+}
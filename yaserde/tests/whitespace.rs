@@ -0,0 +1,98 @@
+#[macro_use]
+extern crate yaserde_derive;
+
+use yaserde::de::{Config, Deserializer, Parser};
+use yaserde::YaDeserialize;
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+const PEM: &str = "
+-----BEGIN CERTIFICATE-----
+MIIBszCCAVmgAwIBAgIUQ
+  indented line
+-----END CERTIFICATE-----
+";
+
+#[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+#[yaserde(rename = "certificate")]
+pub struct Certificate {
+  #[yaserde(text = true)]
+  content: String,
+}
+
+#[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+#[yaserde(rename = "server")]
+pub struct Server {
+  name: String,
+  certificate: Certificate,
+  script: String,
+}
+
+fn content() -> String {
+  format!(
+    r#"<server xml:space="preserve">
+  <name> main </name>
+  <certificate>{}</certificate>
+  <script xml:space="default">   trimmed   </script>
+</server>"#,
+    PEM
+  )
+}
+
+#[test]
+fn xml_space_preserve() {
+  init();
+
+  let model = Server {
+    name: " main ".to_string(),
+    certificate: Certificate {
+      content: PEM.to_string(),
+    },
+    script: "trimmed".to_string(),
+  };
+
+  let loaded: Server = yaserde::de::from_str(&content()).unwrap();
+  assert_eq!(loaded, model);
+
+  // Written back as is
+  let content = yaserde::ser::to_string(&model.certificate).unwrap();
+  let loaded: Certificate = yaserde::de::from_str(
+    &content.replace("<certificate>", r#"<certificate xml:space="preserve">"#),
+  )
+  .unwrap();
+  assert_eq!(loaded, model.certificate);
+}
+
+#[test]
+#[cfg(feature = "quick-xml")]
+fn xml_space_preserve_quick_xml() {
+  init();
+
+  let content = content();
+  let loaded: Server = yaserde::de::from_str_with_parser(&content, Parser::QuickXml).unwrap();
+
+  assert_eq!(loaded.name, " main ");
+  assert_eq!(loaded.certificate.content, PEM);
+  assert_eq!(loaded.script, "trimmed");
+}
+
+#[test]
+fn trim_whitespace_disabled() {
+  init();
+
+  let content = "<certificate>  one\n  two  </certificate>";
+  let config = Config {
+    parser: Parser::XmlRs,
+    trim_whitespace: false,
+    ..Default::default()
+  };
+
+  let mut deserializer = Deserializer::new_from_str_with_config(content, &config);
+  let loaded = Certificate::deserialize(&mut deserializer).unwrap();
+  assert_eq!(loaded.content, "  one\n  two  ");
+
+  let loaded: Certificate = yaserde::de::from_str(content).unwrap();
+  assert_eq!(loaded.content, "one\n  two");
+}
@@ -0,0 +1,239 @@
+#[macro_use]
+extern crate yaserde;
+#[macro_use]
+extern crate yaserde_derive;
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Date {
+  year: u16,
+  month: u8,
+  day: u8,
+}
+
+mod iso_date {
+  use super::Date;
+
+  pub fn serialize(date: &Date) -> Result<String, String> {
+    Ok(format!(
+      "{:04}-{:02}-{:02}",
+      date.year, date.month, date.day
+    ))
+  }
+
+  pub fn deserialize(value: &str) -> Result<Date, String> {
+    let mut parts = value.splitn(3, '-').map(|part| part.parse().ok());
+
+    match (parts.next(), parts.next(), parts.next()) {
+      (Some(Some(year)), Some(Some(month)), Some(Some(day))) => Ok(Date {
+        year,
+        month: month as u8,
+        day: day as u8,
+      }),
+      _ => Err(format!("invalid date {:?}", value)),
+    }
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Action {
+  Allow,
+  Deny,
+}
+
+fn action_to_string(action: &Action) -> Result<String, String> {
+  Ok(match action {
+    Action::Allow => "pass".to_string(),
+    Action::Deny => "block".to_string(),
+  })
+}
+
+fn action_from_string(value: &str) -> Result<Action, String> {
+  match value {
+    "pass" | "allow" => Ok(Action::Allow),
+    "block" | "deny" => Ok(Action::Deny),
+    _ => Err(format!("unknown action {:?}", value)),
+  }
+}
+
+fn upper(value: &str) -> Result<String, String> {
+  Ok(value.to_uppercase())
+}
+
+#[test]
+fn with_element() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "event")]
+  pub struct Event {
+    #[yaserde(with = "iso_date")]
+    start: Date,
+    #[yaserde(with = "iso_date")]
+    end: Option<Date>,
+    #[yaserde(with = "iso_date")]
+    holidays: Vec<Date>,
+    #[yaserde(
+      serialize_with = "action_to_string",
+      deserialize_with = "action_from_string"
+    )]
+    action: Action,
+  }
+
+  let model = Event {
+    start: Date {
+      year: 2020,
+      month: 1,
+      day: 2,
+    },
+    end: None,
+    holidays: vec![
+      Date {
+        year: 2020,
+        month: 12,
+        day: 25,
+      },
+      Date {
+        year: 2021,
+        month: 1,
+        day: 1,
+      },
+    ],
+    action: Action::Deny,
+  };
+
+  let content = r#"
+    <event>
+      <start>2020-01-02</start>
+      <holidays>2020-12-25</holidays>
+      <holidays>2021-01-01</holidays>
+      <action>block</action>
+    </event>"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Event);
+
+  let content = r#"
+    <event>
+      <start>2020-01-02</start>
+      <end>2020-01-03</end>
+      <action>deny</action>
+    </event>"#;
+
+  let model = Event {
+    start: Date {
+      year: 2020,
+      month: 1,
+      day: 2,
+    },
+    end: Some(Date {
+      year: 2020,
+      month: 1,
+      day: 3,
+    }),
+    holidays: vec![],
+    action: Action::Deny,
+  };
+
+  deserialize_and_validate!(content, model, Event);
+}
+
+#[test]
+fn with_element_error() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize)]
+  #[yaserde(rename = "event")]
+  pub struct Event {
+    #[yaserde(with = "iso_date")]
+    start: Date,
+    #[yaserde(with = "iso_date")]
+    end: Option<Date>,
+  }
+
+  let loaded: Result<Event, String> = yaserde::de::from_str("<event><start>x1</start></event>");
+  assert_eq!(loaded, Err("invalid date \"x1\"".to_string()));
+
+  let loaded: Result<Event, String> =
+    yaserde::de::from_str("<event><start>2020-01-02</start><end>x1</end></event>");
+  assert_eq!(loaded, Err("invalid date \"x1\"".to_string()));
+
+  // Empty elements are given to the function too
+  let loaded: Result<Event, String> = yaserde::de::from_str("<event><start/></event>");
+  assert_eq!(loaded, Err("invalid date \"\"".to_string()));
+}
+
+#[test]
+fn with_attribute() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "rule")]
+  pub struct Rule {
+    #[yaserde(
+      attribute = true,
+      serialize_with = "action_to_string",
+      deserialize_with = "action_from_string"
+    )]
+    action: Action,
+    #[yaserde(attribute = true, with = "iso_date")]
+    expires: Option<Date>,
+    #[yaserde(attribute = true, deserialize_with = "upper")]
+    name: String,
+  }
+
+  let model = Rule {
+    action: Action::Allow,
+    expires: Some(Date {
+      year: 2030,
+      month: 6,
+      day: 30,
+    }),
+    name: "SSH".to_string(),
+  };
+
+  serialize_and_validate!(
+    model,
+    r#"<rule action="pass" expires="2030-06-30" name="SSH" />"#
+  );
+  deserialize_and_validate!(
+    r#"<rule action="allow" expires="2030-06-30" name="ssh" />"#,
+    model,
+    Rule
+  );
+
+  let loaded: Result<Rule, String> =
+    yaserde::de::from_str(r#"<rule action="reject" name="ssh" />"#);
+  assert_eq!(loaded, Err("unknown action \"reject\"".to_string()));
+}
+
+#[test]
+fn with_text() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "date")]
+  pub struct DateNode {
+    #[yaserde(attribute = true)]
+    kind: String,
+    #[yaserde(text = true, with = "iso_date")]
+    value: Date,
+  }
+
+  let model = DateNode {
+    kind: "start".to_string(),
+    value: Date {
+      year: 1999,
+      month: 12,
+      day: 31,
+    },
+  };
+
+  let content = r#"<date kind="start">1999-12-31</date>"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, DateNode);
+}
@@ -1,4 +1,4 @@
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::{Ident, TokenStream, TokenTree};
 use quote::{quote, ToTokens};
 use serde::Deserialize;
 use serde_tokenstream::from_tokenstream;
@@ -11,9 +11,21 @@ pub struct YaSerdeAttribute {
   /// Set this field as an XML attribute
   #[serde(default)]
   pub attribute: bool,
+  /// Set the representation of booleans: `bool`, `int`, `yesno` or `onoff`
+  #[serde(default)]
+  pub bool_format: Option<String>,
+  /// Collect the comments before the element of the struct in this `Vec<String>` field
+  #[serde(default)]
+  pub comments: bool,
   /// Set default callback function
   #[serde(default)]
   pub default: Option<String>,
+  /// Set a custom function to parse the field value
+  #[serde(default)]
+  pub deserialize_with: Option<String>,
+  /// Write the element without content as `self_closing` (`<a />`) or `explicit` (`<a></a>`)
+  #[serde(default)]
+  pub empty: Option<String>,
   /// Set the default namespace
   #[serde(default)]
   pub default_namespace: Option<String>,
@@ -26,9 +38,18 @@ pub struct YaSerdeAttribute {
   /// Set the prefix for the scope
   #[serde(default)]
   pub prefix: Option<String>,
+  /// Set a boolean field from the presence of an empty element
+  #[serde(default)]
+  pub presence: bool,
   /// Rename the field/struct/enum name
   #[serde(default)]
   pub rename: Option<String>,
+  /// Set a custom function to format the field value
+  #[serde(default)]
+  pub serialize_with: Option<String>,
+  /// Set the separator used to join and split list items
+  #[serde(default)]
+  pub separator: Option<String>,
   #[serde(default)]
   pub tag: Option<String>,
   /// Disable the serialization for the field
@@ -43,6 +64,12 @@ pub struct YaSerdeAttribute {
   /// Set the field as an XML CDATA content
   #[serde(default)]
   pub cdata: bool,
+  /// Trim whitespace around separated list items
+  #[serde(default)]
+  pub trim: bool,
+  /// Set a module providing both `serialize` and `deserialize` functions
+  #[serde(default)]
+  pub with: Option<String>,
 }
 
 impl TryFrom<&Attribute> for YaSerdeAttribute {
@@ -58,7 +85,7 @@ impl TryFrom<&Attribute> for YaSerdeAttribute {
           let mut tokens = TokenStream::new();
           list.tokens.to_tokens(&mut tokens);
 
-          match from_tokenstream::<YaSerdeAttribute>(&tokens) {
+          match from_tokenstream::<YaSerdeAttribute>(&expand_flags(tokens)) {
             Ok(attribute) => attribute,
             Err(error) => {
               panic!("YaSerDe derive error: {}", error);
@@ -77,6 +104,58 @@ impl TryFrom<&Attribute> for YaSerdeAttribute {
   }
 }
 
+/// Expand the bare `presence` flag into `presence = true`
+fn expand_flags(tokens: TokenStream) -> TokenStream {
+  let mut expanded = TokenStream::new();
+  let mut item: Vec<TokenTree> = vec![];
+
+  let push_item = |item: &mut Vec<TokenTree>, expanded: &mut TokenStream| {
+    if let [TokenTree::Ident(flag)] = item.as_slice() {
+      if flag == "presence" {
+        expanded.extend(quote!(#flag = true));
+      } else {
+        expanded.extend(item.iter().cloned());
+      }
+    } else {
+      expanded.extend(item.iter().cloned());
+    }
+    item.clear();
+  };
+
+  for token in tokens {
+    match token {
+      TokenTree::Punct(ref punct) if punct.as_char() == ',' => {
+        push_item(&mut item, &mut expanded);
+        expanded.extend(std::iter::once(token));
+      }
+      token => item.push(token),
+    }
+  }
+  push_item(&mut item, &mut expanded);
+
+  expanded
+}
+
+/// The true and false values of a `bool_format`
+fn bool_format_values(format: &str) -> Option<(&'static str, &'static str)> {
+  match format {
+    "bool" => Some(("true", "false")),
+    "int" => Some(("1", "0")),
+    "yesno" => Some(("yes", "no")),
+    "onoff" => Some(("on", "off")),
+    _ => None,
+  }
+}
+
+/// The `EmptyElement` variant of an `empty` value
+fn empty_element_value(empty: &str) -> Option<TokenStream> {
+  match empty {
+    "self_closing" => Some(quote!(::yaserde::ser::EmptyElement::SelfClosing)),
+    "explicit" => Some(quote!(::yaserde::ser::EmptyElement::Explicit)),
+    _ => None,
+  }
+}
+
 impl From<&Vec<Attribute>> for YaSerdeAttribute {
   fn from(attributes: &Vec<Attribute>) -> Self {
     attributes
@@ -87,10 +166,93 @@ impl From<&Vec<Attribute>> for YaSerdeAttribute {
 }
 
 impl YaSerdeAttribute {
+  /// Report the invalid values of the `#[yaserde]` attribute in `attributes`
+  pub fn check(attributes: &[Attribute]) -> syn::Result<()> {
+    for attribute in attributes {
+      if let Meta::List(list) = &attribute.meta {
+        if attribute.path().is_ident("yaserde") {
+          from_tokenstream::<YaSerdeAttribute>(&expand_flags(list.tokens.clone()))?;
+        }
+      }
+    }
+
+    let attribute = match attributes
+      .iter()
+      .find(|attribute| attribute.path().is_ident("yaserde"))
+    {
+      Some(attribute) => attribute,
+      None => return Ok(()),
+    };
+    let yaserde_attribute = YaSerdeAttribute::from(&attributes.to_vec());
+
+    if let Some(format) = &yaserde_attribute.bool_format {
+      if bool_format_values(format).is_none() {
+        return Err(syn::Error::new_spanned(
+          attribute,
+          format!(
+            "unknown bool_format {:?}, expected \"bool\", \"int\", \"yesno\" or \"onoff\"",
+            format
+          ),
+        ));
+      }
+    }
+
+    if let Some(empty) = &yaserde_attribute.empty {
+      if empty_element_value(empty).is_none() {
+        return Err(syn::Error::new_spanned(
+          attribute,
+          format!(
+            "unknown empty {:?}, expected \"self_closing\" or \"explicit\"",
+            empty
+          ),
+        ));
+      }
+    }
+
+    if yaserde_attribute.separator.as_deref() == Some("") {
+      return Err(syn::Error::new_spanned(
+        attribute,
+        "separator can't be empty",
+      ));
+    }
+
+    let functions = [
+      ("with", &yaserde_attribute.with),
+      ("serialize_with", &yaserde_attribute.serialize_with),
+      ("deserialize_with", &yaserde_attribute.deserialize_with),
+    ];
+    for (name, function) in functions {
+      if let Some(function) = function {
+        if syn::parse_str::<syn::Path>(function).is_err() {
+          return Err(syn::Error::new_spanned(
+            attribute,
+            format!("invalid {} path {:?}", name, function),
+          ));
+        }
+      }
+    }
+
+    Ok(())
+  }
+
   pub fn xml_element_name(&self, ident: &Ident) -> String {
     self.rename.clone().unwrap_or_else(|| ident.to_string())
   }
 
+  pub fn bool_values(&self) -> Option<(String, String)> {
+    self.bool_format.as_ref().map(|format| {
+      // Unknown formats are reported by `check`
+      let (true_value, false_value) = bool_format_values(format).unwrap_or(("true", "false"));
+
+      (true_value.to_string(), false_value.to_string())
+    })
+  }
+
+  pub fn empty_element(&self) -> Option<TokenStream> {
+    // Unknown values are reported by `check`
+    self.empty.as_deref().and_then(empty_element_value)
+  }
+
   pub fn prefix_namespace(&self) -> String {
     if self.default_namespace == self.prefix {
       "".to_string()
@@ -102,6 +264,41 @@ impl YaSerdeAttribute {
     }
   }
 
+  /// URI of `prefix`, or of the default namespace without prefix. The `xml`
+  /// and `xmlns` prefixes are reserved. `None` when the prefix is not declared.
+  pub fn namespace_uri(&self, prefix: Option<&str>) -> Option<String> {
+    let prefix = prefix.or(self.default_namespace.as_deref())?;
+
+    self
+      .namespaces
+      .get(prefix)
+      .cloned()
+      .or_else(|| match prefix {
+        "xml" => Some("http://www.w3.org/XML/1998/namespace".to_string()),
+        "xmlns" => Some("http://www.w3.org/2000/xmlns/".to_string()),
+        _ => None,
+      })
+  }
+
+  /// Whether `attr` is the attribute `local_name` with `prefix`. Attributes
+  /// without prefix are in no namespace, so the default namespace doesn't
+  /// apply. Undeclared prefixes are compared as written in the document.
+  pub fn attribute_matching(&self, prefix: Option<&str>, local_name: &str) -> TokenStream {
+    let namespace = match prefix {
+      None => quote!(attr.name.namespace.is_none()),
+      Some(prefix) if self.default_namespace.as_deref() == Some(prefix) => {
+        // Written without prefix by the serializer
+        quote!(attr.name.namespace.is_none())
+      }
+      Some(prefix) => match self.namespace_uri(Some(prefix)) {
+        Some(uri) => quote!(attr.name.namespace.as_deref() == ::std::option::Option::Some(#uri)),
+        None => quote!(attr.name.prefix.as_deref() == ::std::option::Option::Some(#prefix)),
+      },
+    };
+
+    quote!(attr.name.local_name == #local_name && #namespace)
+  }
+
   pub fn get_namespace_matching(
     &self,
     prefix: &Option<String>,
@@ -110,32 +307,24 @@ impl YaSerdeAttribute {
     take_root_prefix: bool,
   ) -> TokenStream {
     let configured_prefix = if take_root_prefix {
-      self.prefix.clone()
+      self.prefix.as_deref()
     } else {
-      prefix.clone()
+      prefix.as_deref()
     };
 
-    let namespaces_matches: TokenStream = self
-      .namespaces
-      .iter()
-      .filter_map(|(prefix, namespace)| {
-        if configured_prefix.as_deref().eq(&Some(prefix)) {
-          Some(quote!(#namespace => {}))
-        } else {
-          None
-        }
-      })
-      .collect();
+    let namespace = match self.namespace_uri(configured_prefix) {
+      Some(namespace) => namespace,
+      // Matched by prefix
+      None if configured_prefix.is_some() => return quote!(),
+      None => String::new(),
+    };
 
     quote!(
       if let Some(namespace) = #element_namespace {
-        match namespace.as_str() {
-          #namespaces_matches
-          bad_namespace => {
-            let msg =
-              ::std::format!("bad namespace for {}, found {}", #element_name, bad_namespace);
-            return Err(msg);
-          }
+        if namespace.as_str() != #namespace {
+          let msg =
+            ::std::format!("bad namespace for {}, found {}", #element_name, namespace);
+          return Err(msg);
         }
       }
     )
@@ -0,0 +1,232 @@
+use crate::common::{YaSerdeAttribute, YaSerdeField};
+
+/// Report what the derive can't expand, with the span of the faulty tokens,
+/// before the expansion starts
+pub fn check(ast: &syn::DeriveInput) -> syn::Result<()> {
+  YaSerdeAttribute::check(&ast.attrs)?;
+  if let syn::Data::Enum(data_enum) = &ast.data {
+    for variant in &data_enum.variants {
+      YaSerdeAttribute::check(&variant.attrs)?;
+    }
+  }
+
+  let fields: Vec<&syn::Field> = match &ast.data {
+    syn::Data::Struct(data_struct) => data_struct.fields.iter().collect(),
+    syn::Data::Enum(data_enum) => data_enum
+      .variants
+      .iter()
+      .flat_map(|variant| variant.fields.iter())
+      .collect(),
+    syn::Data::Union(_) => vec![],
+  };
+
+  for field in fields {
+    YaSerdeAttribute::check(&field.attrs)?;
+    YaSerdeField::new(field.clone()).check()?;
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use syn::parse_quote;
+
+  fn error(ast: syn::DeriveInput) -> String {
+    check(&ast).unwrap_err().to_string()
+  }
+
+  #[test]
+  fn nested_options_and_sequences() {
+    let message = "Option and sequence fields can't hold an Option or a sequence";
+    assert_eq!(
+      error(parse_quote! {
+        struct Tags {
+          #[yaserde(attribute = true)]
+          tags: Vec<Option<String>>,
+        }
+      }),
+      message
+    );
+    assert_eq!(
+      error(parse_quote! {
+        struct Tags {
+          tags: HashSet<Option<String>>,
+        }
+      }),
+      message
+    );
+    assert_eq!(
+      error(parse_quote! {
+        struct Tags {
+          tags: VecDeque<Vec<String>>,
+        }
+      }),
+      message
+    );
+  }
+
+  #[test]
+  fn unknown_bool_format() {
+    assert_eq!(
+      error(parse_quote! {
+        struct Switch {
+          #[yaserde(bool_format = "truefalse")]
+          on: bool,
+        }
+      }),
+      r#"unknown bool_format "truefalse", expected "bool", "int", "yesno" or "onoff""#
+    );
+  }
+
+  #[test]
+  fn unknown_empty() {
+    assert_eq!(
+      error(parse_quote! {
+        struct Item {
+          #[yaserde(empty = "open")]
+          note: String,
+        }
+      }),
+      r#"unknown empty "open", expected "self_closing" or "explicit""#
+    );
+  }
+
+  #[test]
+  fn invalid_function_path() {
+    assert_eq!(
+      error(parse_quote! {
+        struct Event {
+          #[yaserde(deserialize_with = "date::parse(")]
+          start: String,
+        }
+      }),
+      r#"invalid deserialize_with path "date::parse(""#
+    );
+    assert_eq!(
+      error(parse_quote! {
+        struct Event {
+          #[yaserde(with = "date-time")]
+          start: String,
+        }
+      }),
+      r#"invalid with path "date-time""#
+    );
+  }
+
+  #[test]
+  fn invalid_separator() {
+    assert_eq!(
+      error(parse_quote! {
+        struct Tags {
+          #[yaserde(attribute = true, separator = "")]
+          tags: Vec<String>,
+        }
+      }),
+      "separator can't be empty"
+    );
+    assert_eq!(
+      error(parse_quote! {
+        struct Tags {
+          #[yaserde(attribute = true, separator = ",")]
+          tags: String,
+        }
+      }),
+      "separator and trim apply to sequence attributes and text"
+    );
+    assert_eq!(
+      error(parse_quote! {
+        struct Tags {
+          #[yaserde(trim = true)]
+          tags: Vec<String>,
+        }
+      }),
+      "separator and trim apply to sequence attributes and text"
+    );
+  }
+
+  #[test]
+  fn presence_of_option() {
+    assert_eq!(
+      error(parse_quote! {
+        struct Rule {
+          #[yaserde(presence)]
+          disabled: Option<bool>,
+        }
+      }),
+      "presence fields must be bool"
+    );
+  }
+
+  #[test]
+  fn comments_of_string() {
+    assert_eq!(
+      error(parse_quote! {
+        struct Rule {
+          #[yaserde(comments = true)]
+          comments: String,
+        }
+      }),
+      "comments fields must be Vec<String>"
+    );
+  }
+
+  #[test]
+  fn bare_flags() {
+    // Only `presence` can be written without `= true`
+    assert!(check(&parse_quote! {
+      struct Rule {
+        #[yaserde(presence)]
+        disabled: bool,
+      }
+    })
+    .is_ok());
+    assert!(check(&parse_quote! {
+      struct Rule {
+        #[yaserde(attribute)]
+        name: String,
+      }
+    })
+    .is_err());
+  }
+
+  #[test]
+  fn own_collection_types() {
+    use crate::common::Field;
+
+    let field = |ty: syn::Type| Field::from(&ty);
+
+    assert!(matches!(
+      field(parse_quote!(std::collections::HashSet<String>)),
+      Field::FieldVec { .. }
+    ));
+    assert!(matches!(
+      field(parse_quote!(std::collections::hash_set::HashSet<String>)),
+      Field::FieldVec { .. }
+    ));
+    assert!(matches!(
+      field(parse_quote!(
+        ::alloc::collections::btree_set::BTreeSet<String>
+      )),
+      Field::FieldVec { .. }
+    ));
+    assert!(matches!(
+      field(parse_quote!(HashSet)),
+      Field::FieldStruct { .. }
+    ));
+    assert!(matches!(
+      field(parse_quote!(my::Vec<String>)),
+      Field::FieldStruct { .. }
+    ));
+
+    // Not sequences, so accepted as attributes
+    let ast = parse_quote! {
+      struct Library {
+        #[yaserde(attribute = true)]
+        shelves: my::Vec<Option<String>>,
+      }
+    };
+    assert!(check(&ast).is_ok());
+  }
+}
@@ -36,6 +36,67 @@ impl YaSerdeField {
     self.attributes.flatten
   }
 
+  pub fn is_comments(&self) -> bool {
+    self.attributes.comments
+  }
+
+  fn is_string_vec(&self) -> bool {
+    match self.get_type() {
+      Field::FieldVec { data_type } => matches!(
+        *data_type,
+        Field::FieldString {
+          kind: StringKind::Owned
+        }
+      ),
+      _ => false,
+    }
+  }
+
+  /// Report the field types the derive can't expand
+  pub fn check(&self) -> syn::Result<()> {
+    if self.attributes.presence && !matches!(self.get_type(), Field::FieldBool) {
+      return Err(syn::Error::new_spanned(
+        &self.syn_field.ty,
+        "presence fields must be bool",
+      ));
+    }
+
+    if self.attributes.comments && !self.is_string_vec() {
+      return Err(syn::Error::new_spanned(
+        &self.syn_field.ty,
+        "comments fields must be Vec<String>",
+      ));
+    }
+
+    if (self.attributes.separator.is_some() || self.attributes.trim)
+      && !(matches!(self.get_type(), Field::FieldVec { .. })
+        && (self.is_attribute() || self.is_text_content()))
+    {
+      return Err(syn::Error::new_spanned(
+        &self.syn_field.ty,
+        "separator and trim apply to sequence attributes and text",
+      ));
+    }
+
+    if let Field::FieldOption { data_type } | Field::FieldVec { data_type } = self.get_type() {
+      if matches!(
+        *data_type,
+        Field::FieldOption { .. } | Field::FieldVec { .. }
+      ) {
+        return Err(syn::Error::new_spanned(
+          &self.syn_field.ty,
+          "Option and sequence fields can't hold an Option or a sequence",
+        ));
+      }
+    }
+
+    Ok(())
+  }
+
+  pub fn is_presence(&self) -> bool {
+    self.attributes.presence
+  }
+
   pub fn label(&self) -> Option<Ident> {
     self.syn_field.ident.clone()
   }
@@ -48,6 +109,41 @@ impl YaSerdeField {
     self.attributes.cdata
   }
 
+  pub fn has_empty_element(&self) -> bool {
+    self.attributes.empty.is_some()
+  }
+
+  /// Set how the elements of the field are written without content, before serializing it
+  pub fn ser_empty_element(&self) -> TokenStream {
+    match self.attributes.empty_element() {
+      Some(empty_element) => {
+        quote!(writer.set_empty_element(::std::option::Option::Some(#empty_element));)
+      }
+      None => quote!(writer.set_empty_element(::std::option::Option::None);),
+    }
+  }
+
+  pub fn get_separator(&self) -> String {
+    self
+      .attributes
+      .separator
+      .clone()
+      .unwrap_or_else(|| " ".to_string())
+  }
+
+  /// Build an iterator over the `&str` items of a separated list
+  pub fn split_list_items(&self, source: TokenStream) -> TokenStream {
+    match (&self.attributes.separator, self.attributes.trim) {
+      (None, _) => quote!(#source.split_whitespace()),
+      (Some(separator), true) => quote!(
+        #source.split(#separator).map(|item| item.trim()).filter(|item| !item.is_empty())
+      ),
+      (Some(separator), false) => quote!(
+        #source.split(#separator).filter(|_| !#source.is_empty())
+      ),
+    }
+  }
+
   pub fn get_value_label(&self) -> Option<syn::Ident> {
     self
       .syn_field
@@ -101,11 +197,11 @@ impl YaSerdeField {
       .map(|p| format!("{}_", p.to_upper_camel_case()))
       .unwrap_or_default();
 
-    let attribute = self
-      .attributes
-      .attribute
-      .then_some("Attribute_".to_string())
-      .unwrap_or_default();
+    let attribute = if self.attributes.attribute {
+      "Attribute_".to_string()
+    } else {
+      String::new()
+    };
 
     Ident::new(
       &format!(
@@ -122,6 +218,49 @@ impl YaSerdeField {
     Field::from(&self.syn_field)
   }
 
+  /// Type of the field value, or of each item for `Option` and sequence fields
+  pub fn get_item_type(&self) -> TokenStream {
+    let item_type = match self.get_type() {
+      Field::FieldOption { data_type } | Field::FieldVec { data_type } => *data_type,
+      field_type => field_type,
+    };
+
+    match item_type {
+      Field::FieldStruct { struct_name } => quote!(#struct_name),
+      // Reported by `YaSerdeField::check`
+      Field::FieldOption { .. } | Field::FieldVec { .. } => unreachable!(),
+      simple_type => simple_type.into(),
+    }
+  }
+
+  /// Get the value type of a `Nullable<T>` field
+  pub fn get_nullable_type(&self) -> Option<Field> {
+    match self.get_type() {
+      Field::FieldStruct { struct_name } => struct_name
+        .segments
+        .last()
+        .filter(|segment| segment.ident == "Nullable")
+        .map(Field::from),
+      _ => None,
+    }
+  }
+
+  /// Whether the field collects `ProcessingInstruction`s rather than elements
+  pub fn is_processing_instruction(&self) -> bool {
+    let item_type = match self.get_type() {
+      Field::FieldOption { data_type } | Field::FieldVec { data_type } => *data_type,
+      field_type => field_type,
+    };
+
+    match item_type {
+      Field::FieldStruct { struct_name } => struct_name
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "ProcessingInstruction"),
+      _ => false,
+    }
+  }
+
   pub fn get_span(&self) -> Span {
     self.syn_field.span()
   }
@@ -142,18 +281,82 @@ impl YaSerdeField {
       .map(|skip_serializing_if| Ident::new(skip_serializing_if, self.get_span()))
   }
 
-  pub fn prefix_namespace(&self, root_attributes: &YaSerdeAttribute) -> String {
-    root_attributes
-      .namespaces
-      .iter()
-      .find_map(|(prefix, namespace)| {
-        if self.attributes.prefix.as_deref().eq(&Some(prefix)) {
-          Some(namespace.clone())
-        } else {
-          None
-        }
-      })
-      .unwrap_or_default()
+  pub fn is_bool(&self) -> bool {
+    match self.get_type() {
+      Field::FieldOption { data_type } | Field::FieldVec { data_type } => {
+        matches!(*data_type, Field::FieldBool)
+      }
+      field_type => matches!(field_type, Field::FieldBool),
+    }
+  }
+
+  pub fn get_bool_values(&self, root_attributes: &YaSerdeAttribute) -> (String, String) {
+    self
+      .attributes
+      .bool_values()
+      .or_else(|| root_attributes.bool_values())
+      .unwrap_or_else(|| ("true".to_string(), "false".to_string()))
+  }
+
+  /// Convert a boolean value (or a reference to it) to a string
+  pub fn ser_bool(&self, value: TokenStream, root_attributes: &YaSerdeAttribute) -> TokenStream {
+    let (true_value, false_value) = self.get_bool_values(root_attributes);
+    quote!(::yaserde::primitives::serialize_bool(&#value, #true_value, #false_value))
+  }
+
+  /// Parse a boolean from a `&str`
+  pub fn de_bool(&self, value: TokenStream, root_attributes: &YaSerdeAttribute) -> TokenStream {
+    let (true_value, false_value) = self.get_bool_values(root_attributes);
+    quote!(::yaserde::primitives::deserialize_bool(#value, #true_value, #false_value))
+  }
+
+  pub fn get_serialize_with_function(&self) -> Option<TokenStream> {
+    self.get_with_function(&self.attributes.serialize_with, "serialize")
+  }
+
+  pub fn get_deserialize_with_function(&self) -> Option<TokenStream> {
+    self.get_with_function(&self.attributes.deserialize_with, "deserialize")
+  }
+
+  fn get_with_function(
+    &self,
+    function: &Option<String>,
+    module_function: &str,
+  ) -> Option<TokenStream> {
+    let path = function.clone().or_else(|| {
+      self
+        .attributes
+        .with
+        .as_ref()
+        .map(|module| format!("{}::{}", module, module_function))
+    })?;
+
+    // Reported by `YaSerdeAttribute::check`
+    let path = syn::parse_str::<syn::Path>(&path).expect("function path");
+
+    Some(quote!(#path))
+  }
+
+  /// Match arm pattern for the `(namespace, local_name)` of the element of the
+  /// field. Elements with an undeclared prefix are compared by prefix.
+  pub fn element_pattern(&self, root_attributes: &YaSerdeAttribute) -> TokenStream {
+    let label_name = self.renamed_label_without_namespace();
+    let prefix = self.attributes.prefix.as_deref();
+
+    match (root_attributes.namespace_uri(prefix), prefix) {
+      (Some(namespace), _) => quote!((#namespace, #label_name)),
+      (None, Some(prefix)) => {
+        quote!((_, #label_name) if name.prefix.as_deref() == ::std::option::Option::Some(#prefix))
+      }
+      (None, None) => quote!(("", #label_name)),
+    }
+  }
+
+  pub fn attribute_matching(&self, root_attributes: &YaSerdeAttribute) -> TokenStream {
+    root_attributes.attribute_matching(
+      self.attributes.prefix.as_deref(),
+      &self.renamed_label_without_namespace(),
+    )
   }
 
   pub fn get_namespace_matching(
@@ -206,10 +409,21 @@ impl YaSerdeField {
   }
 }
 
+/// How a string field holds its content
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StringKind {
+  /// `String`
+  Owned,
+  /// `&'de str`, borrowed from the input
+  Borrowed,
+  /// `Cow<'de, str>`, borrowed from the input when possible
+  Cow,
+}
+
 #[derive(Debug)]
 #[allow(clippy::enum_variant_names)]
 pub enum Field {
-  FieldString,
+  FieldString { kind: StringKind },
   FieldBool,
   FieldI8,
   FieldU8,
@@ -230,13 +444,68 @@ impl Field {
   pub fn get_simple_type_visitor(&self) -> Ident {
     format_ident!("visit_{}", self.to_string())
   }
+
+  /// Visitor method to call with the `Cow` text of an element or an attribute
+  pub fn get_text_visitor(&self) -> Ident {
+    match self {
+      Field::FieldString {
+        kind: StringKind::Borrowed | StringKind::Cow,
+      } => format_ident!("visit_cow_str"),
+      _ => self.get_simple_type_visitor(),
+    }
+  }
+
+  /// Visitor methods for string types that borrow from the input
+  pub fn get_borrowed_visitor_methods(&self) -> Option<TokenStream> {
+    let (visit_borrowed, visit_owned) = match self {
+      Field::FieldString {
+        kind: StringKind::Borrowed,
+      } => (
+        quote!(::std::result::Result::Ok(v)),
+        quote!(::std::result::Result::Err(::std::format!(
+          "unable to borrow {:?} from the input, &str fields need Parser::QuickXml and text without escapes",
+          v
+        ))),
+      ),
+      Field::FieldString {
+        kind: StringKind::Cow,
+      } => (
+        quote!(::std::result::Result::Ok(::std::borrow::Cow::Borrowed(v))),
+        quote!(::std::result::Result::Ok(::std::borrow::Cow::Owned(
+          v.to_owned()
+        ))),
+      ),
+      _ => return None,
+    };
+
+    Some(quote! {
+      fn visit_borrowed_str(
+        self,
+        v: &'de str,
+      ) -> ::std::result::Result<Self::Value, ::std::string::String> {
+        #visit_borrowed
+      }
+
+      fn visit_str(
+        self,
+        v: &str,
+      ) -> ::std::result::Result<Self::Value, ::std::string::String> {
+        #visit_owned
+      }
+    })
+  }
 }
 
 impl From<&syn::Path> for Field {
   fn from(path: &syn::Path) -> Self {
     let result = if let Some(segment) = path.segments.last() {
       match segment.ident.to_string().as_str() {
-        "String" => Some(Field::FieldString),
+        "String" => Some(Field::FieldString {
+          kind: StringKind::Owned,
+        }),
+        "Cow" if is_std_type(path) && is_cow_str(segment) => Some(Field::FieldString {
+          kind: StringKind::Cow,
+        }),
         "bool" => Some(Field::FieldBool),
         "i8" => Some(Field::FieldI8),
         "u8" => Some(Field::FieldU8),
@@ -251,9 +520,16 @@ impl From<&syn::Path> for Field {
         "Option" => Some(Field::FieldOption {
           data_type: Box::new(Field::from(segment)),
         }),
-        "Vec" => Some(Field::FieldVec {
+        "Vec" if is_std_type(path) && has_type_argument(segment) => Some(Field::FieldVec {
           data_type: Box::new(Field::from(segment)),
         }),
+        "VecDeque" | "LinkedList" | "HashSet" | "BTreeSet"
+          if is_std_type(path) && has_type_argument(segment) =>
+        {
+          Some(Field::FieldVec {
+            data_type: Box::new(Field::from(segment)),
+          })
+        }
         _ => None,
       }
     } else {
@@ -266,15 +542,59 @@ impl From<&syn::Path> for Field {
   }
 }
 
+/// Whether `path` is the last segment alone, or a path in the standard
+/// library, like `std::collections::hash_set::HashSet`
+fn is_std_type(path: &syn::Path) -> bool {
+  match path.segments.first() {
+    Some(_) if path.segments.len() == 1 => path.leading_colon.is_none(),
+    Some(krate) => krate.ident == "std" || krate.ident == "alloc",
+    None => false,
+  }
+}
+
+/// Whether a collection segment has an item type, like `Vec<T>`
+fn has_type_argument(segment: &syn::PathSegment) -> bool {
+  match &segment.arguments {
+    syn::PathArguments::AngleBracketed(args) => args
+      .args
+      .iter()
+      .any(|arg| matches!(arg, syn::GenericArgument::Type(_))),
+    _ => false,
+  }
+}
+
+/// Whether a `Cow` segment is `Cow<'a, str>`
+fn is_cow_str(segment: &syn::PathSegment) -> bool {
+  match &segment.arguments {
+    syn::PathArguments::AngleBracketed(args) => matches!(
+      args.args.last(),
+      Some(syn::GenericArgument::Type(Path(ty))) if ty.path.is_ident("str")
+    ),
+    _ => false,
+  }
+}
+
 impl From<&syn::Field> for Field {
   fn from(field: &syn::Field) -> Self {
-    let mut ty = &field.ty;
+    Field::from(&field.ty)
+  }
+}
+
+impl From<&syn::Type> for Field {
+  fn from(field_type: &syn::Type) -> Self {
+    let mut ty = field_type;
     while let syn::Type::Group(g) = ty {
       ty = &g.elem;
     }
     match ty {
       Path(ref path) => Field::from(&path.path),
-      _ => panic!("unable to match {:?}", field.ty),
+      syn::Type::Reference(syn::TypeReference { elem, .. }) => match elem.as_ref() {
+        Path(path) if path.path.is_ident("str") => Field::FieldString {
+          kind: StringKind::Borrowed,
+        },
+        _ => panic!("unable to match {:?}", field_type),
+      },
+      _ => panic!("unable to match {:?}", field_type),
     }
   }
 }
@@ -283,13 +603,10 @@ impl From<&syn::PathSegment> for Field {
   fn from(path_segment: &syn::PathSegment) -> Self {
     if let syn::PathArguments::AngleBracketed(ref args) = path_segment.arguments {
       match args.args.first() {
-        Some(syn::GenericArgument::Type(Path(ref path))) => {
-          return Field::from(&path.path);
-        }
-        Some(syn::GenericArgument::Type(syn::Type::Group(syn::TypeGroup { elem, .. }))) => {
-          if let Path(ref group) = elem.as_ref() {
-            return Field::from(&group.path);
-          }
+        Some(syn::GenericArgument::Type(
+          ty @ (Path(_) | syn::Type::Group(_) | syn::Type::Reference(_)),
+        )) => {
+          return Field::from(ty);
         }
         _ => unimplemented!("unable to match '{:?}'", args.args.first()),
       }
@@ -302,7 +619,15 @@ impl From<&syn::PathSegment> for Field {
 impl From<Field> for proc_macro2::TokenStream {
   fn from(field: Field) -> proc_macro2::TokenStream {
     match field {
-      Field::FieldString => quote! { ::std::string::String },
+      Field::FieldString {
+        kind: StringKind::Owned,
+      } => quote! { ::std::string::String },
+      Field::FieldString {
+        kind: StringKind::Borrowed,
+      } => quote! { &'de str },
+      Field::FieldString {
+        kind: StringKind::Cow,
+      } => quote! { ::std::borrow::Cow<'de, str> },
       Field::FieldBool => quote! { bool },
       Field::FieldI8 => quote! { i8 },
       Field::FieldU8 => quote! { u8 },
@@ -322,7 +647,7 @@ impl From<Field> for proc_macro2::TokenStream {
 impl From<&Field> for String {
   fn from(field: &Field) -> String {
     match field {
-      Field::FieldString => "str".to_string(),
+      Field::FieldString { .. } => "str".to_string(),
       Field::FieldBool => "bool".to_string(),
       Field::FieldI8 => "i8".to_string(),
       Field::FieldU8 => "u8".to_string(),
@@ -1,5 +1,7 @@
 mod attribute;
+mod check;
 mod field;
 
 pub use attribute::YaSerdeAttribute;
-pub use field::{Field, YaSerdeField};
+pub use check::check;
+pub use field::{Field, StringKind, YaSerdeField};
@@ -1,3 +1,4 @@
+use super::with_de_lifetime;
 use crate::common::{Field, YaSerdeAttribute, YaSerdeField};
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
@@ -20,38 +21,52 @@ pub fn parse(
   let match_to_enum: TokenStream = data_enum
     .variants
     .iter()
-    .filter_map(|variant| parse_variant(variant, name))
+    .filter_map(|variant| parse_variant(variant, name, root_attributes))
     .collect();
 
   let flatten = root_attributes.flatten;
-  let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+  let de_generics = with_de_lifetime(generics);
+  let (impl_generics, _, _) = de_generics.split_for_impl();
+  let (_, ty_generics, where_clause) = generics.split_for_impl();
 
+  // Variants are matched on `(namespace, local_name)`, the namespace is only
+  // set for prefixed names and values
   let element_name = if let Some(tag) = &root_attributes.tag {
+    let (prefix, local_name) = match tag.split_once(':') {
+      Some((prefix, local_name)) => (Some(prefix), local_name),
+      None => (None, tag.as_str()),
+    };
+    let attribute_matching = root_attributes.attribute_matching(prefix, local_name);
+
     quote! {
-      attributes
-        .iter()
-        .find(|attr| attr.name.local_name.as_str() == #tag)
-        .ok_or(format!("Expected enum tagged with {}, found {:?}", #tag, event))?
-        .value.as_str()
+      ::yaserde::de::resolve_qname(
+        &attributes
+          .iter()
+          .find(|attr| #attribute_matching)
+          .ok_or(format!("Expected enum tagged with {}, found {:?}", #tag, event))?
+          .value,
+        namespace,
+      )
     }
   } else {
     quote! {
-      name.local_name.as_str()
+      (
+        name.prefix.as_ref().and(name.namespace.as_deref()),
+        name.local_name.as_str(),
+      )
     }
   };
 
   quote! {
-    impl #impl_generics ::yaserde::YaDeserialize for #name #ty_generics #where_clause {
+    impl #impl_generics ::yaserde::YaDeserialize<'de> for #name #ty_generics #where_clause {
       #[allow(unused_variables)]
       fn deserialize<R: ::std::io::Read>(
-        reader: &mut ::yaserde::de::Deserializer<R>,
+        reader: &mut ::yaserde::de::Deserializer<'de, R>,
       ) -> ::std::result::Result<Self, ::std::string::String> {
-        let (named_element, enum_namespace) =
-          if let ::yaserde::__xml::reader::XmlEvent::StartElement{ name, .. } = reader.peek()?.to_owned() {
-            (name.local_name.to_owned(), name.namespace.clone())
-          } else {
-            (::std::string::String::from(#root), ::std::option::Option::None)
-          };
+        let (named_element, enum_namespace) = match reader.peek_name()? {
+          ::std::option::Option::Some(name) => (name.local_name.clone(), name.namespace.clone()),
+          ::std::option::Option::None => (::std::string::String::from(#root), ::std::option::Option::None),
+        };
 
         let start_depth = reader.depth();
         ::yaserde::__derive_debug!("Enum {} @ {}: start to parse {:?}", stringify!(#name), start_depth, named_element);
@@ -60,37 +75,44 @@ pub fn parse(
 
         #[allow(unused_assignments, unused_mut)]
         let mut enum_value = ::std::option::Option::None;
+        // Namespaces in scope to resolve the prefix of text values
+        let mut scope = ::yaserde::events::Namespace::empty();
 
         loop {
-          let event = reader.peek()?.to_owned();
+          let reader_depth = reader.depth();
+          let event = reader.peek()?;
           ::yaserde::__derive_trace!("Enum {} @ {}: matching {:?}", stringify!(#name), start_depth, event);
           match event {
-            ::yaserde::__xml::reader::XmlEvent::StartElement { ref name, ref attributes, .. } => {
+            ::yaserde::events::ReadEvent::StartElement { name, attributes, namespace } => {
               match #element_name {
                 #match_to_enum
                 _named_element => {
-                  let _root = reader.next_event();
+                  if let ::std::result::Result::Ok(
+                    ::yaserde::events::ReadEvent::StartElement { namespace, .. },
+                  ) = reader.next_event() {
+                    scope = namespace;
+                  }
                 }
               }
 
-              if let ::yaserde::__xml::reader::XmlEvent::Characters(content) = reader.peek()?.to_owned() {
-                match content.as_str() {
+              if let ::yaserde::events::ReadEvent::Characters(content) | ::yaserde::events::ReadEvent::CData(content) = reader.peek()? {
+                match ::yaserde::de::resolve_qname(content, &scope) {
                   #match_to_enum
                   _ => {}
                 }
               }
             }
-            ::yaserde::__xml::reader::XmlEvent::EndElement { ref name } => {
-                log::trace!("expand_enum struct deserialize macro Got EndElement {}", name.local_name);
-              if name.local_name == named_element && reader.depth() == start_depth + 1 {
+            ::yaserde::events::ReadEvent::EndElement { name } => {
+                ::yaserde::__derive_trace!("expand_enum struct deserialize macro Got EndElement {}", name.local_name);
+              if name.local_name == named_element && reader_depth == start_depth + 1 {
                 break;
               }
               let _root = reader.next_event();
             }
-            ::yaserde::__xml::reader::XmlEvent::Characters(ref text_content) => {
+            ::yaserde::events::ReadEvent::Characters(_) | ::yaserde::events::ReadEvent::CData(_) => {
               let _root = reader.next_event();
             }
-            ::yaserde::__xml::reader::XmlEvent::EndDocument => {
+            ::yaserde::events::ReadEvent::EndDocument => {
               if #flatten {
                 break;
               }
@@ -112,8 +134,24 @@ pub fn parse(
   }
 }
 
-fn parse_variant(variant: &syn::Variant, name: &Ident) -> Option<TokenStream> {
-  let xml_element_name = YaSerdeAttribute::from(&variant.attrs).xml_element_name(&variant.ident);
+fn parse_variant(
+  variant: &syn::Variant,
+  name: &Ident,
+  root_attributes: &YaSerdeAttribute,
+) -> Option<TokenStream> {
+  let variant_attributes = YaSerdeAttribute::from(&variant.attrs);
+  let xml_element_name = variant_attributes.xml_element_name(&variant.ident);
+  // Unprefixed names and values match whatever the namespace of the variant
+  let xml_element_name = match (
+    root_attributes.namespace_uri(variant_attributes.prefix.as_deref()),
+    &variant_attributes.prefix,
+  ) {
+    (Some(namespace), _) => quote! {
+      (::std::option::Option::Some(#namespace) | ::std::option::Option::None, #xml_element_name)
+    },
+    (None, Some(_)) => quote!((_, #xml_element_name)),
+    (None, None) => quote!((::std::option::Option::None, #xml_element_name)),
+  };
 
   let variant_name = {
     let label = &variant.ident;
@@ -128,7 +166,7 @@ fn parse_variant(variant: &syn::Variant, name: &Ident) -> Option<TokenStream> {
       }
     }),
     Fields::Unnamed(ref fields) => {
-      let field_visitors = build_unnamed_field_visitors(fields);
+      let field_visitors = build_unnamed_field_visitors(fields, root_attributes);
       let call_visitors = build_unnamed_visitor_calls(fields, &variant_name);
 
       if fields.unnamed.len() > 1 {
@@ -155,7 +193,10 @@ fn parse_variant(variant: &syn::Variant, name: &Ident) -> Option<TokenStream> {
   }
 }
 
-fn build_unnamed_field_visitors(fields: &syn::FieldsUnnamed) -> TokenStream {
+fn build_unnamed_field_visitors(
+  fields: &syn::FieldsUnnamed,
+  root_attributes: &YaSerdeAttribute,
+) -> TokenStream {
   fields
     .unnamed
     .iter()
@@ -182,14 +223,29 @@ fn build_unnamed_field_visitors(fields: &syn::FieldsUnnamed) -> TokenStream {
       };
 
       let simple_type_visitor = |simple_type: Field| {
+        if let Some(visitor_methods) = simple_type.get_borrowed_visitor_methods() {
+          let field_type = TokenStream::from(simple_type);
+
+          return quote! {
+            #[allow(non_snake_case, non_camel_case_types)]
+            struct #visitor_label;
+            impl<'de> ::yaserde::Visitor<'de> for #visitor_label {
+              type Value = #field_type;
+
+              #visitor_methods
+            }
+          };
+        }
+
         let visitor = simple_type.get_simple_type_visitor();
         let field_type = simple_type.into();
+        let fn_body = if field.is_bool() {
+          field.de_bool(quote!(v), root_attributes)
+        } else {
+          quote! { ::std::result::Result::Ok(#field_type::from_str(v).unwrap()) }
+        };
 
-        make_visitor(
-          &visitor,
-          &field_type,
-          &quote! { ::std::result::Result::Ok(#field_type::from_str(v).unwrap()) },
-        )
+        make_visitor(&visitor, &field_type, &fn_body)
       };
 
       match field.get_type() {
@@ -234,7 +290,12 @@ fn build_unnamed_visitor_calls(
       let visitor_label = Ident::new(&format!("__Visitor_{}", idx), field.get_span());
 
       let call_simple_type_visitor = |simple_type: Field, action| {
-        let visitor = simple_type.get_simple_type_visitor();
+        let visitor = simple_type.get_text_visitor();
+        let empty = if visitor == "visit_cow_str" {
+          quote!(&::std::borrow::Cow::Borrowed(""))
+        } else {
+          quote!("")
+        };
         let field_type: TokenStream = simple_type.into();
 
         let label_name = format!("field_{}", idx);
@@ -243,11 +304,11 @@ fn build_unnamed_visitor_calls(
           let visitor = #visitor_label{};
 
           let result = reader.read_inner_value::<#field_type, _>(|reader| {
-            if let ::yaserde::__xml::reader::XmlEvent::EndElement { .. } = *reader.peek()? {
-              return visitor.#visitor("");
+            if let ::yaserde::events::ReadEvent::EndElement { .. } = *reader.peek()? {
+              return visitor.#visitor(#empty);
             }
 
-            if let ::std::result::Result::Ok(::yaserde::__xml::reader::XmlEvent::Characters(s))
+            if let ::std::result::Result::Ok(::yaserde::events::ReadEvent::Characters(s) | ::yaserde::events::ReadEvent::CData(s))
               = reader.next_event()
             {
               visitor.#visitor(&s)
@@ -287,7 +348,7 @@ fn build_unnamed_visitor_calls(
       let set_vec = quote! {
         match enum_value {
           Some(ref mut v) => match v {
-            #variant_name(ref mut v) => v.push(value),
+            #variant_name(ref mut v) => v.extend(::std::iter::once(value)),
             _ => {
               return ::std::result::Result::Err(
                 ::std::string::String::from("Got sequence of different types"),
@@ -295,7 +356,7 @@ fn build_unnamed_visitor_calls(
             }
           }
           None => {
-            enum_value = ::std::option::Option::Some(#variant_name(vec![value]));
+            enum_value = ::std::option::Option::Some(#variant_name(::std::iter::once(value).collect()));
           }
         }
       };
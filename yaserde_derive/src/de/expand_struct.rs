@@ -1,5 +1,6 @@
 use super::build_default_value::{build_default_value, build_default_vec_value};
-use crate::common::{Field, YaSerdeAttribute, YaSerdeField};
+use super::with_de_lifetime;
+use crate::common::{Field, StringKind, YaSerdeAttribute, YaSerdeField};
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::{DataStruct, Generics, Ident};
@@ -52,17 +53,43 @@ pub fn parse(
     .iter()
     .map(|field| YaSerdeField::new(field.clone()))
     .filter(|field| {
-      if field.is_attribute() {
+      !field.is_presence() && !field.is_processing_instruction() && !field.is_comments()
+    })
+    .filter(|field| {
+      if field.is_attribute() || field.get_deserialize_with_function().is_some() {
         return true;
       };
       match field.get_type() {
         Field::FieldVec { data_type } => !matches!(*data_type, Field::FieldStruct { .. }),
         Field::FieldOption { data_type } => !matches!(*data_type, Field::FieldStruct { .. }),
-        Field::FieldStruct { .. } => false,
+        Field::FieldStruct { .. } => !matches!(
+          field.get_nullable_type(),
+          None | Some(Field::FieldStruct { .. })
+        ),
         _ => true,
       }
     })
     .filter_map(|field| {
+      if let Some(deserialize_with) = field.get_deserialize_with_function() {
+        let visitor_label = field.get_visitor_ident(None);
+        let field_type = field.get_item_type();
+
+        return Some(quote! {
+          #[allow(non_snake_case, non_camel_case_types)]
+          struct #visitor_label;
+          impl<'de> ::yaserde::Visitor<'de> for #visitor_label {
+            type Value = #field_type;
+
+            fn visit_str(
+              self,
+              v: &str,
+            ) -> ::std::result::Result<Self::Value, ::std::string::String> {
+              #deserialize_with(v)
+            }
+          }
+        });
+      }
+
       let struct_visitor = |struct_name: syn::Path| {
         let struct_id: String = struct_name
           .segments
@@ -95,16 +122,27 @@ pub fn parse(
       let simple_type_visitor = |simple_type: Field| {
         let visitor = simple_type.get_simple_type_visitor();
         let visitor_label = field.get_visitor_ident(None);
+
+        if let Some(visitor_methods) = simple_type.get_borrowed_visitor_methods() {
+          let field_type = TokenStream::from(simple_type);
+
+          return Some(quote! {
+            #[allow(non_snake_case, non_camel_case_types)]
+            struct #visitor_label;
+            impl<'de> ::yaserde::Visitor<'de> for #visitor_label {
+              type Value = #field_type;
+
+              #visitor_methods
+            }
+          });
+        }
+
         let field_type = TokenStream::from(simple_type);
 
-        let map_if_bool = if field_type.to_string() == "bool" {
-          quote!(match v {
-            "1" => "true",
-            "0" => "false",
-            _ => v,
-          })
+        let parse = if field.is_bool() {
+          field.de_bool(quote!(v), root_attributes)
         } else {
-          quote!(v)
+          quote!(#field_type::from_str(v).map_err(|e| e.to_string()))
         };
 
         Some(quote! {
@@ -117,12 +155,16 @@ pub fn parse(
               self,
               v: &str,
             ) -> ::std::result::Result<Self::Value, ::std::string::String> {
-              #field_type::from_str(#map_if_bool).map_err(|e| e.to_string())
+              #parse
             }
           }
         })
       };
 
+      if let Some(simple_type) = field.get_nullable_type() {
+        return simple_type_visitor(simple_type);
+      }
+
       match field.get_type() {
         Field::FieldStruct { struct_name } => struct_visitor(struct_name),
         Field::FieldOption { data_type } => match *data_type {
@@ -144,38 +186,114 @@ pub fn parse(
     .fields
     .iter()
     .map(|field| YaSerdeField::new(field.clone()))
-    .filter(|field| !field.is_attribute() && !field.is_flatten())
+    .filter(|field| {
+      !field.is_attribute()
+        && !field.is_flatten()
+        && !field.is_processing_instruction()
+        && !field.is_comments()
+    })
     .filter_map(|field| {
       let value_label = field.get_value_label();
-      let label_name = field.renamed_label_without_namespace();
+      let pattern = field.element_pattern(root_attributes);
+
+      if field.is_presence() {
+        let namespaces_matching = field.get_namespace_matching(
+          root_attributes,
+          quote!(name.namespace.as_ref()),
+          quote!(name.local_name.as_str()),
+        );
 
-      let namespace = field.prefix_namespace(root_attributes);
+        return Some(quote! {
+          #pattern => {
+            #namespaces_matching
+
+            reader.read_inner_value::<(), _>(|reader| {
+              if let ::std::result::Result::Ok(::yaserde::events::ReadEvent::Characters(_) | ::yaserde::events::ReadEvent::CData(_)) = reader.peek() {
+                let _event = reader.next_event()?;
+              }
+              ::std::result::Result::Ok(())
+            })?;
+
+            #value_label = ::std::option::Option::Some(true);
+          }
+        });
+      }
+
+      match field.get_nullable_type() {
+        None | Some(Field::FieldStruct { .. }) => {}
+        Some(simple_type) => {
+          let visitor = simple_type.get_text_visitor();
+          let visitor_label = field.get_visitor_ident(None);
+          let field_type = TokenStream::from(simple_type);
+          let namespaces_matching = field.get_namespace_matching(
+            root_attributes,
+            quote!(name.namespace.as_ref()),
+            quote!(name.local_name.as_str()),
+          );
+
+          return Some(quote! {
+            #pattern => {
+              let visitor = #visitor_label{};
+
+              #namespaces_matching
+
+              let value = reader.read_inner_value::<::yaserde::Nullable<#field_type>, _>(|reader| {
+                if let ::std::result::Result::Ok(::yaserde::events::ReadEvent::Characters(s) | ::yaserde::events::ReadEvent::CData(s)) = reader.peek() {
+                  let value = visitor.#visitor(&s)?;
+                  let _event = reader.next_event()?;
+                  ::std::result::Result::Ok(::yaserde::Nullable::Value(value))
+                } else {
+                  ::std::result::Result::Ok(::yaserde::Nullable::Empty)
+                }
+              })?;
+
+              #value_label = ::std::option::Option::Some(value);
+            }
+          });
+        }
+      }
+
+      if field.get_deserialize_with_function().is_some() {
+        let action = match field.get_type() {
+          Field::FieldVec { .. } => quote! { .push(value) },
+          _ => quote! { = ::std::option::Option::Some(value) },
+        };
+
+        return build_call_visitor(
+          &field.get_item_type(),
+          &Ident::new("visit_str", field.get_span()),
+          &action,
+          &field,
+          root_attributes,
+          true,
+        );
+      }
 
       let visit_struct = |struct_name: syn::Path, action: TokenStream| {
         Some(quote! {
-          (#namespace, #label_name) => {
+          #pattern => {
             if depth == 0 {
               // Don't count current struct's StartElement as substruct's StartElement
               let _root = reader.next_event();
             }
 
             ::yaserde::__derive_debug!("Looking at startElement");
-            if let Ok(::yaserde::__xml::reader::XmlEvent::StartElement { .. }) = reader.peek() {
+            if let Ok(::yaserde::events::ReadEvent::StartElement { .. }) = reader.peek() {
               // If substruct's start element found then deserialize substruct
-              log::debug!("Found start element ?? {}", stringify!(#struct_name));
+              ::yaserde::__derive_debug!("Found start element ?? {}", stringify!(#struct_name));
               let value = <#struct_name as ::yaserde::YaDeserialize>::deserialize(reader)?;
               #value_label #action;
               // read EndElement
               let _event = reader.next_event()?;
             } else {
-              log::debug!("matching field type did not find substruct start element ? {}", stringify!(#struct_name));
+              ::yaserde::__derive_debug!("matching field type did not find substruct start element ? {}", stringify!(#struct_name));
             }
           }
         })
       };
 
       let visit_simple = |simple_type: Field, action: TokenStream| {
-        let field_visitor = simple_type.get_simple_type_visitor();
+        let field_visitor = simple_type.get_text_visitor();
         let field_type = TokenStream::from(simple_type);
         build_call_visitor(
           &field_type,
@@ -183,6 +301,7 @@ pub fn parse(
           &action,
           &field,
           root_attributes,
+          false,
         )
       };
 
@@ -224,11 +343,11 @@ pub fn parse(
 
       match field.get_type() {
         Field::FieldStruct { .. } => quote! {
-          #value_label = Some(::yaserde::de::from_str(&unused_xml_elements)?);
+          #value_label = Some(::yaserde::de::from_flattened_str(&unused_xml_elements)?);
         },
         Field::FieldOption { data_type } => match *data_type {
           Field::FieldStruct { .. } => quote! {
-            #value_label = ::yaserde::de::from_str(&unused_xml_elements).ok();
+            #value_label = ::yaserde::de::from_flattened_str(&unused_xml_elements).ok();
           },
           field_type => unimplemented!(r#""flatten" is not implemented for {:?}"#, field_type),
         },
@@ -237,6 +356,41 @@ pub fn parse(
     })
     .collect();
 
+  let visit_processing_instructions = data_struct
+    .fields
+    .iter()
+    .map(|field| YaSerdeField::new(field.clone()))
+    .find(|field| !field.is_attribute() && field.is_processing_instruction())
+    .map(|field| {
+      let value_label = field.get_value_label();
+      let item_type = field.get_item_type();
+      let action = match field.get_type() {
+        Field::FieldVec { .. } => quote! { .push(value) },
+        _ => quote! { = ::std::option::Option::Some(value) },
+      };
+
+      quote! {
+        ::yaserde::events::ReadEvent::ProcessingInstruction { .. } => {
+          let value = <#item_type as ::yaserde::YaDeserialize>::deserialize(reader)?;
+          #value_label #action;
+        }
+      }
+    });
+  let capture_processing_instructions = visit_processing_instructions
+    .as_ref()
+    .map(|_| quote!(reader.capture_processing_instructions();));
+
+  let take_leading_comments: TokenStream = data_struct
+    .fields
+    .iter()
+    .map(|field| YaSerdeField::new(field.clone()))
+    .filter(|field| field.is_comments())
+    .map(|field| {
+      let value_label = field.get_value_label();
+      quote! { #value_label = reader.take_leading_comments(); }
+    })
+    .collect();
+
   let attributes_loading: TokenStream = data_struct
     .fields
     .iter()
@@ -244,13 +398,13 @@ pub fn parse(
     .filter(|field| field.is_attribute())
     .filter_map(|field| {
       let label = field.get_value_label();
-      let label_name = field.renamed_label_without_namespace();
+      let attribute_matching = field.attribute_matching(root_attributes);
       let visitor_label = field.get_visitor_ident(None);
 
       let visit = |action: &TokenStream, visitor: &Ident, visitor_label: &Ident| {
         Some(quote! {
           for attr in attributes {
-            if attr.name.local_name == #label_name {
+            if #attribute_matching {
               let visitor = #visitor_label{};
               let value = visitor.#visitor(&attr.value)?;
               #label #action;
@@ -260,10 +414,12 @@ pub fn parse(
       };
 
       let visit_vec = |action: &TokenStream, visitor: &Ident, visitor_label: &Ident| {
+        let items = field.split_list_items(quote!(attr.value));
+
         Some(quote! {
           for attr in attributes {
-            if attr.name.local_name == #label_name {
-              for value in attr.value.split_whitespace() {
+            if #attribute_matching {
+              for value in #items {
                 let visitor = #visitor_label{};
                 let value = visitor.#visitor(value)?;
                 #label #action;
@@ -276,13 +432,26 @@ pub fn parse(
       let visit_string = || {
         Some(quote! {
           for attr in attributes {
-            if attr.name.local_name == #label_name {
-              #label = Some(attr.value.to_owned());
+            if #attribute_matching {
+              #label = Some(attr.value.to_string());
             }
           }
         })
       };
 
+      if field.get_deserialize_with_function().is_some() {
+        let visitor = Ident::new("visit_str", field.get_span());
+
+        return match field.get_type() {
+          Field::FieldVec { .. } => visit_vec(&quote! { .push(value) }, &visitor, &visitor_label),
+          _ => visit(
+            &quote! { = ::std::option::Option::Some(value) },
+            &visitor,
+            &visitor_label,
+          ),
+        };
+      }
+
       log::trace!("field.get_type {}", field.get_type());
       let visit_struct = |struct_name: syn::Path, action: TokenStream| {
         visit(
@@ -293,11 +462,7 @@ pub fn parse(
       };
 
       let visit_simple = |simple_type: Field, action: TokenStream| {
-        visit(
-          &action,
-          &simple_type.get_simple_type_visitor(),
-          &visitor_label,
-        )
+        visit(&action, &simple_type.get_text_visitor(), &visitor_label)
       };
 
       let visit_sub = |sub_type: Box<Field>, action: TokenStream| match *sub_type {
@@ -308,7 +473,9 @@ pub fn parse(
 
       log::trace!("field.get_type {}", field.get_type());
       match field.get_type() {
-        Field::FieldString => visit_string(),
+        Field::FieldString {
+          kind: StringKind::Owned,
+        } => visit_string(),
         Field::FieldOption { data_type } => {
           visit_sub(data_type, quote! { = ::std::option::Option::Some(value) })
         }
@@ -344,15 +511,76 @@ pub fn parse(
           .then_some(quote! { #label = #action; })
       };
 
+      if field.is_text_content() && field.get_deserialize_with_function().is_some() {
+        let visitor_label = field.get_visitor_ident(None);
+
+        return match field.get_type() {
+          Field::FieldVec { .. } => {
+            let items = field.split_list_items(quote!(text_content));
+
+            Some(quote! {
+              for value in #items {
+                let visitor = #visitor_label{};
+                let value = visitor.visit_str(value)?;
+                #label.push(value);
+              }
+            })
+          }
+          Field::FieldOption { .. } => Some(quote! {
+            if !text_content.is_empty() {
+              let visitor = #visitor_label{};
+              #label = ::std::option::Option::Some(visitor.visit_str(text_content)?);
+            }
+          }),
+          _ => Some(quote! {
+            let visitor = #visitor_label{};
+            #label = ::std::option::Option::Some(visitor.visit_str(text_content)?);
+          }),
+        };
+      }
+
+      let visitor_label = field.get_visitor_ident(None);
+
       match field.get_type() {
-        Field::FieldString => set_text(&quote! { Some(text_content.to_owned()) }),
+        Field::FieldString {
+          kind: StringKind::Owned,
+        } => set_text(&quote! { Some(text_content.to_string()) }),
+        Field::FieldString { .. } => {
+          set_text(&quote! { Some(#visitor_label{}.visit_cow_str(text_content)?) })
+        }
         Field::FieldOption { data_type } => match *data_type {
-          Field::FieldString => set_text(
-            &quote! { if text_content.is_empty() { None } else { Some(text_content.to_owned()) }},
+          Field::FieldString {
+            kind: StringKind::Owned,
+          } => set_text(
+            &quote! { if text_content.is_empty() { None } else { Some(text_content.to_string()) }},
           ),
+          Field::FieldString { .. } => set_text(&quote! {
+            if text_content.is_empty() { None } else { Some(#visitor_label{}.visit_cow_str(text_content)?) }
+          }),
           _ => None,
         },
+        Field::FieldVec { data_type } if field.is_text_content() => match *data_type {
+          Field::FieldStruct { .. } | Field::FieldOption { .. } | Field::FieldVec { .. } => {
+            unimplemented!("text content is not implemented for {:?}", data_type)
+          }
+          simple_type => {
+            let visitor = simple_type.get_simple_type_visitor();
+            let items = field.split_list_items(quote!(text_content));
+
+            Some(quote! {
+              for value in #items {
+                let visitor = #visitor_label{};
+                let value = visitor.#visitor(value)?;
+                #label.push(value);
+              }
+            })
+          }
+        },
         Field::FieldStruct { .. } | Field::FieldVec { .. } => None,
+        Field::FieldBool => {
+          let parse = field.de_bool(quote!(text_content), root_attributes);
+          set_text(&quote! { Some(#parse?) })
+        }
         simple_type => {
           let type_token = TokenStream::from(simple_type);
           set_text(&quote! { #type_token::from_str(text_content).unwrap() })
@@ -374,9 +602,18 @@ pub fn parse(
       };
 
       match field.get_type() {
-        Field::FieldOption { .. } | Field::FieldVec { .. } => {
+        Field::FieldOption { .. } => {
           quote! { #label: #value_label, }
         }
+        Field::FieldVec { .. } => {
+          quote! { #label: #value_label.into_iter().collect(), }
+        }
+        _ if field.is_presence() => {
+          quote! { #label: #value_label.unwrap_or(false), }
+        }
+        _ if field.get_nullable_type().is_some() && field.get_default_function().is_none() => {
+          quote! { #label: #value_label.unwrap_or_default(), }
+        }
         _ => {
           if let Some(default_function) = field.get_default_function() {
             quote! { #label: #value_label.unwrap_or_else(|| #default_function()), }
@@ -405,20 +642,22 @@ pub fn parse(
   };
 
   let flatten = root_attributes.flatten;
-  let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+  // Unknown elements are left to the flattened fields
+  let has_flatten = !call_flatten_visitors.is_empty();
+  let de_generics = with_de_lifetime(generics);
+  let (impl_generics, _, _) = de_generics.split_for_impl();
+  let (_, ty_generics, where_clause) = generics.split_for_impl();
 
   quote! {
-    impl #impl_generics ::yaserde::YaDeserialize for #name #ty_generics #where_clause {
+    impl #impl_generics ::yaserde::YaDeserialize<'de> for #name #ty_generics #where_clause {
       #[allow(unused_variables)]
       fn deserialize<R: ::std::io::Read>(
-        reader: &mut ::yaserde::de::Deserializer<R>,
+        reader: &mut ::yaserde::de::Deserializer<'de, R>,
       ) -> ::std::result::Result<Self, ::std::string::String> {
-        let (named_element, struct_namespace) =
-          if let ::yaserde::__xml::reader::XmlEvent::StartElement { name, .. } = reader.peek()?.to_owned() {
-            (name.local_name.to_owned(), name.namespace.clone())
-          } else {
-            (::std::string::String::from(#root), ::std::option::Option::None)
-          };
+        let (named_element, struct_namespace) = match reader.peek_name()? {
+          ::std::option::Option::Some(name) => (name.local_name.clone(), name.namespace.clone()),
+          ::std::option::Option::None => (::std::string::String::from(#root), ::std::option::Option::None),
+        };
         let start_depth = reader.depth();
         ::yaserde::__derive_debug!("Struct {} @ {}: start to parse {:?}", stringify!(#name), start_depth,
                named_element);
@@ -434,49 +673,51 @@ pub fn parse(
         let mut depth = 0;
 
         loop {
-          let event = reader.peek()?.to_owned();
+          let reader_depth = reader.depth();
+          // Look at the event by reference, it is only consumed once it is no longer borrowed
+          let event = reader.peek()?;
           ::yaserde::__derive_trace!(
             "Struct {} @ {}: matching {:?}",
             stringify!(#name), start_depth, event,
           );
           match event {
-            ::yaserde::__xml::reader::XmlEvent::StartElement{ref name, ref attributes, ..} => {
-              let namespace = name.namespace.clone().unwrap_or_default();
-              if depth == 0 && name.local_name == #root && namespace.as_str() == #root_namespace {
+            ::yaserde::events::ReadEvent::StartElement{name, attributes, ..} => {
+              if depth == 0 { // Look for attributes only at element start
+                #attributes_loading
+              }
+
+              let namespace = name.namespace.as_deref().unwrap_or_default();
+              if depth == 0 && name.local_name == #root && namespace == #root_namespace {
                 // Consume root element. We must do this first. In the case it shares a name with a child element, we don't
                 // want to prematurely match the child element below.
                 let event = reader.next_event()?;
+                #capture_processing_instructions
+                #take_leading_comments
                 #write_unused
               } else {
 
-                match (namespace.as_str(), name.local_name.as_str()) {
+                match (namespace, name.local_name.as_str()) {
                   #call_visitors
                   _ => {
 
                     ::yaserde::__derive_trace!("Got StartElement {:?}", name.local_name);
-                    // return Err(format!("Found unauthorized element {}", name.local_name));
+                    if depth > 0 && !#has_flatten { // Don't skip root element
+                      // Fails unless the deserializer skips unknown elements
+                      reader.skip_element(|_| {})?;
+                      continue;
+                    }
 
                     let event = reader.next_event()?;
                     ::yaserde::__derive_trace!("Next event {:?}", event);
                     #write_unused
-
-                    if depth > 0 { // Don't skip root element
-                      return Err(format!("Found unauthorized element {}", name.local_name));
-                      // reader.skip_element(|event| {
-                      //   #write_unused
-                      // })?;
-                    }
                   }
                 }
               }
-              if depth == 0 { // Look for attributes only at element start
-                #attributes_loading
-              }
               depth += 1;
             }
-            ::yaserde::__xml::reader::XmlEvent::EndElement { ref name } => {
-                log::trace!("endElement {named_element}");
-              if name.local_name == named_element && reader.depth() == start_depth + 1 {
+            ::yaserde::events::ReadEvent::EndElement { name } => {
+                ::yaserde::__derive_trace!("endElement {}", named_element);
+              if name.local_name == named_element && reader_depth == start_depth + 1 {
                 #write_unused
                 break;
               }
@@ -484,16 +725,17 @@ pub fn parse(
               #write_unused
               depth -= 1;
             }
-            ::yaserde::__xml::reader::XmlEvent::EndDocument => {
+            ::yaserde::events::ReadEvent::EndDocument => {
               if #flatten {
                 break;
               }
             }
-            ::yaserde::__xml::reader::XmlEvent::Characters(ref text_content) => {
+            ::yaserde::events::ReadEvent::Characters(text_content) | ::yaserde::events::ReadEvent::CData(text_content) => {
               #set_text
               let event = reader.next_event()?;
               #write_unused
             }
+            #visit_processing_instructions
             event => {
               return ::std::result::Result::Err(::std::format!("unknown event {:?}", event));
             }
@@ -515,6 +757,7 @@ fn build_call_visitor(
   action: &TokenStream,
   field: &YaSerdeField,
   root_attributes: &YaSerdeAttribute,
+  deserialize_with: bool,
 ) -> Option<TokenStream> {
   let value_label = field.get_value_label();
   let label_name = field.renamed_label_without_namespace();
@@ -526,27 +769,48 @@ fn build_call_visitor(
     quote!(name.local_name.as_str()),
   );
 
-  let namespace = field.prefix_namespace(root_attributes);
+  let pattern = field.element_pattern(root_attributes);
+
+  // The errors of `deserialize_with` functions are returned, and they
+  // receive an empty string for an empty element
+  let (no_content, store_value) = if deserialize_with {
+    (
+      quote!(visitor.#visitor("")),
+      quote! {
+        let value = result?;
+        #value_label#action
+      },
+    )
+  } else {
+    (
+      quote!(::std::result::Result::Err(
+        ::std::format!("unable to parse content for {}", #label_name)
+      )),
+      quote! {
+        if let ::std::result::Result::Ok(value) = result {
+          #value_label#action
+        }
+      },
+    )
+  };
 
   Some(quote! {
-    (#namespace, #label_name) => {
+    #pattern => {
       let visitor = #visitor_label{};
 
       #namespaces_matching
 
       let result = reader.read_inner_value::<#field_type, _>(|reader| {
-        if let ::std::result::Result::Ok(::yaserde::__xml::reader::XmlEvent::Characters(s)) = reader.peek() {
+        if let ::std::result::Result::Ok(::yaserde::events::ReadEvent::Characters(s) | ::yaserde::events::ReadEvent::CData(s)) = reader.peek() {
           let val = visitor.#visitor(&s);
           let _event = reader.next_event()?;
           val
         } else {
-          ::std::result::Result::Err(::std::format!("unable to parse content for {}", #label_name))
+          #no_content
         }
       });
 
-      if let ::std::result::Result::Ok(value) = result {
-        #value_label#action
-      }
+      #store_value
     }
   })
 }
@@ -560,12 +824,12 @@ fn build_code_for_unused_xml_events(
 ) {
   (
     Some(quote! {
-      ::yaserde::__derive_debug!("UNUSED UNUSED created eventWriter {:?}", unused_xml_elements);
+      ::yaserde::__derive_debug!("UNUSED UNUSED created eventWriter");
       let mut buf = ::std::vec![];
-      let mut writer = ::std::option::Option::Some(::yaserde::__xml::writer::EventWriter::new(&mut buf));
+      let mut writer = ::std::option::Option::Some(::yaserde::ser::Serializer::new_for_inner(&mut buf));
     }),
     Some(quote! {
-      ::yaserde::__derive_debug!("UNUSED UNUSED writing to writer or something {:?}", unused_xml_elements);
+      ::yaserde::__derive_debug!("UNUSED UNUSED writing to writer or something");
       if let ::std::option::Option::Some(ref mut w) = writer {
         if w.write(event.as_writer_event().unwrap()).is_err() {
           writer = ::std::option::Option::None;
@@ -573,7 +837,7 @@ fn build_code_for_unused_xml_events(
       }
     }),
     Some(quote! {
-      ::yaserde::__derive_debug!("UNUSED UNUSED Handling unused_xml_elements {:?}", unused_xml_elements);
+      ::yaserde::__derive_debug!("UNUSED UNUSED Handling unused_xml_elements");
       if writer.is_some() {
         let unused_xml_elements = ::std::string::String::from_utf8(buf).unwrap();
         #call_flatten_visitors
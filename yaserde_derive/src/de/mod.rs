@@ -2,11 +2,14 @@ pub mod build_default_value;
 pub mod expand_enum;
 pub mod expand_struct;
 
-use crate::common::YaSerdeAttribute;
-use proc_macro2::TokenStream;
+use crate::common::{self, YaSerdeAttribute};
+use proc_macro2::{Span, TokenStream};
 use quote::quote;
+use syn::{GenericParam, Generics, Lifetime, LifetimeParam};
+
+pub fn expand_derive_deserialize(ast: &syn::DeriveInput) -> syn::Result<TokenStream> {
+  common::check(ast)?;
 
-pub fn expand_derive_deserialize(ast: &syn::DeriveInput) -> Result<TokenStream, String> {
   let name = &ast.ident;
   let attrs = &ast.attrs;
   let data = &ast.data;
@@ -16,15 +19,7 @@ pub fn expand_derive_deserialize(ast: &syn::DeriveInput) -> Result<TokenStream,
 
   let root_name = root_attributes.xml_element_name(name);
   let root_namespace = root_attributes
-    .namespaces
-    .iter()
-    .find_map(|(prefix, namespace)| {
-      if root_attributes.prefix.as_deref().eq(&Some(prefix)) {
-        Some(namespace.clone())
-      } else {
-        None
-      }
-    })
+    .namespace_uri(root_attributes.prefix.as_deref())
     .unwrap_or_default();
 
   let impl_block = match *data {
@@ -52,3 +47,17 @@ pub fn expand_derive_deserialize(ast: &syn::DeriveInput) -> Result<TokenStream,
     };
   })
 }
+
+/// Add the `'de` lifetime of the input, which outlives the lifetimes of the type
+fn with_de_lifetime(generics: &Generics) -> Generics {
+  let mut de_lifetime = LifetimeParam::new(Lifetime::new("'de", Span::call_site()));
+  de_lifetime
+    .bounds
+    .extend(generics.lifetimes().map(|param| param.lifetime.clone()));
+
+  let mut generics = generics.clone();
+  generics
+    .params
+    .insert(0, GenericParam::Lifetime(de_lifetime));
+  generics
+}
@@ -19,7 +19,7 @@ pub fn derive_deserialize(input: TokenStream) -> TokenStream {
 
   match de::expand_derive_deserialize(&ast) {
     Ok(expanded) => expanded.into(),
-    Err(msg) => panic!("{}", msg),
+    Err(error) => error.to_compile_error().into(),
   }
 }
 
@@ -29,7 +29,7 @@ pub fn derive_serialize(input: TokenStream) -> TokenStream {
 
   match ser::expand_derive_serialize(&ast) {
     Ok(expanded) => expanded.into(),
-    Err(msg) => panic!("{}", msg),
+    Err(error) => error.to_compile_error().into(),
   }
 }
 
@@ -27,12 +27,12 @@ pub fn primitive_yaserde(input: TokenStream) -> TokenStream {
 
           fn serialize_attributes(
               &self,
-              attributes: ::std::vec::Vec<::yaserde::__xml::attribute::OwnedAttribute>,
-              namespace: ::yaserde::__xml::namespace::Namespace,
+              attributes: ::std::vec::Vec<::yaserde::events::OwnedAttribute>,
+              namespace: ::yaserde::events::Namespace,
           ) -> ::std::result::Result<
               (
-                  ::std::vec::Vec<::yaserde::__xml::attribute::OwnedAttribute>,
-                  ::yaserde::__xml::namespace::Namespace,
+                  ::std::vec::Vec<::yaserde::events::OwnedAttribute>,
+                  ::yaserde::events::Namespace,
               ),
               ::std::string::String,
           > {
@@ -40,9 +40,9 @@ pub fn primitive_yaserde(input: TokenStream) -> TokenStream {
           }
       }
 
-      impl ::yaserde::YaDeserialize for #struct_name {
+      impl<'de> ::yaserde::YaDeserialize<'de> for #struct_name {
           fn deserialize<R: ::std::io::Read>(
-              reader: &mut ::yaserde::de::Deserializer<R>,
+              reader: &mut ::yaserde::de::Deserializer<'de, R>,
           ) -> ::std::result::Result<Self, ::std::string::String> {
               ::yaserde::primitives::deserialize_primitives(
                   reader,
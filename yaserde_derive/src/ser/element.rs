@@ -14,16 +14,28 @@ pub fn enclose_characters(label: &Option<Ident>, label_name: String) -> TokenStr
   enclose_xml_event(label_name, quote!(format!("{}", self.#label)))
 }
 
+pub fn enclose_string_characters(value: TokenStream, label_name: String) -> TokenStream {
+  enclose_xml_event(label_name, value)
+}
+
+pub fn enclose_serialized_characters(
+  value: TokenStream,
+  label_name: String,
+  serialize_with: &TokenStream,
+) -> TokenStream {
+  enclose_xml_event(label_name, quote!(#serialize_with(#value)?))
+}
+
 fn enclose_xml_event(label_name: String, yaserde_format: TokenStream) -> TokenStream {
   quote! {
-    let start_event = ::yaserde::__xml::writer::XmlEvent::start_element(#label_name);
+    let start_event = ::yaserde::events::WriteEvent::start_element(#label_name);
     writer.write(start_event).map_err(|e| e.to_string())?;
 
     let yaserde_value = #yaserde_format;
-    let data_event = ::yaserde::__xml::writer::XmlEvent::characters(&yaserde_value);
+    let data_event = ::yaserde::events::WriteEvent::characters(&yaserde_value);
     writer.write(data_event).map_err(|e| e.to_string())?;
 
-    let end_event = ::yaserde::__xml::writer::XmlEvent::end_element();
+    let end_event = ::yaserde::events::WriteEvent::end_element();
     writer.write(end_event).map_err(|e| e.to_string())?;
   }
 }
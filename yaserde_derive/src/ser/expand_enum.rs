@@ -33,13 +33,15 @@ pub fn serialize(
         quote! {
           match self {
             #name::#label { .. } => {
-              let tag = ::yaserde::__xml::name::OwnedName::local(#tag);
-              child_attributes.push(::yaserde::__xml::attribute::OwnedAttribute::new(tag, #element_name));
+              let tag = ::yaserde::events::OwnedName::local(#tag);
+              child_attributes.push(::yaserde::events::OwnedAttribute::new(tag, #element_name));
             }
             _ => {}
           }
         }
-      } else { quote!() };
+      } else {
+        quote!()
+      };
 
       let all_fields = variant
         .fields
@@ -54,7 +56,7 @@ pub fn serialize(
         })
         .collect();
 
-      let add_attributes : TokenStream = attribute_fields
+      let add_attributes: TokenStream = attribute_fields
         .iter()
         .map(|field| {
           let label = variant.ident.clone();
@@ -96,7 +98,7 @@ pub fn serialize(
         })
         .collect();
 
-        quote!( #add_attributes #add_tag)
+      quote!( #add_attributes #add_tag)
     })
     .collect();
 
@@ -105,6 +107,7 @@ pub fn serialize(
     root,
     root_attributes,
     quote!(#variant_matches),
+    quote!(),
     quote!(match self {
       #inner_enum_inspector
     }),
@@ -133,7 +136,7 @@ fn inner_enum_inspector(
           } else {
             quote! {
               #name::#label => {
-                let data_event = ::yaserde::__xml::writer::XmlEvent::characters(#label_name);
+                let data_event = ::yaserde::events::WriteEvent::characters(#label_name);
                 writer.write(data_event).map_err(|e| e.to_string())?;
               }
             }
@@ -150,7 +153,7 @@ fn inner_enum_inspector(
 
               if field.is_text_content() {
                 return Some(quote!(
-                  let data_event = ::yaserde::__xml::writer::XmlEvent::characters(&self.#field_label);
+                  let data_event = ::yaserde::events::WriteEvent::characters(&self.#field_label);
                   writer.write(data_event).map_err(|e| e.to_string())?;
                 ));
               }
@@ -158,7 +161,7 @@ fn inner_enum_inspector(
               let field_label_name = field.renamed_label(root_attributes);
 
               match field.get_type() {
-                Field::FieldString
+                Field::FieldString { .. }
                 | Field::FieldBool
                 | Field::FieldU8
                 | Field::FieldI8
@@ -170,18 +173,24 @@ fn inner_enum_inspector(
                 | Field::FieldU64
                 | Field::FieldI64
                 | Field::FieldF64 => Some({
+                  let to_string = if field.is_bool() {
+                    field.ser_bool(quote!(#field_label), root_attributes)
+                  } else {
+                    quote!(#field_label.to_string())
+                  };
+
                   quote! {
                     match self {
                       &#name::#label { ref #field_label, .. } => {
                         let struct_start_event =
-                          ::yaserde::__xml::writer::XmlEvent::start_element(#field_label_name);
+                          ::yaserde::events::WriteEvent::start_element(#field_label_name);
                         writer.write(struct_start_event).map_err(|e| e.to_string())?;
 
-                        let string_value = #field_label.to_string();
-                        let data_event = ::yaserde::__xml::writer::XmlEvent::characters(&string_value);
+                        let string_value = #to_string;
+                        let data_event = ::yaserde::events::WriteEvent::characters(&string_value);
                         writer.write(data_event).map_err(|e| e.to_string())?;
 
-                        let struct_end_event = ::yaserde::__xml::writer::XmlEvent::end_element();
+                        let struct_end_event = ::yaserde::events::WriteEvent::end_element();
                         writer.write(struct_end_event).map_err(|e| e.to_string())?;
                       },
                       _ => {},
@@ -234,26 +243,34 @@ fn inner_enum_inspector(
             .map(|field| {
               let write_element = |action: &TokenStream| {
                 quote! {
-                  let struct_start_event = ::yaserde::__xml::writer::XmlEvent::start_element(#label_name);
+                  let struct_start_event = ::yaserde::events::WriteEvent::start_element(#label_name);
                   writer.write(struct_start_event).map_err(|e| e.to_string())?;
 
                   #action
 
-                  let struct_end_event = ::yaserde::__xml::writer::XmlEvent::end_element();
+                  let struct_end_event = ::yaserde::events::WriteEvent::end_element();
                   writer.write(struct_end_event).map_err(|e| e.to_string())?;
                 }
               };
 
               let write_string_chars = quote! {
-                let data_event = ::yaserde::__xml::writer::XmlEvent::characters(item);
+                let data_event = ::yaserde::events::WriteEvent::characters(item);
                 writer.write(data_event).map_err(|e| e.to_string())?;
               };
 
-              let write_simple_type = write_element(&quote! {
-                let s = item.to_string();
-                let data_event = ::yaserde::__xml::writer::XmlEvent::characters(&s);
+              let to_string = if field.is_bool() {
+                field.ser_bool(quote!(item), root_attributes)
+              } else {
+                quote!(item.to_string())
+              };
+
+              let write_simple_chars = quote! {
+                let s = #to_string;
+                let data_event = ::yaserde::events::WriteEvent::characters(&s);
                 writer.write(data_event).map_err(|e| e.to_string())?;
-              });
+              };
+
+              let write_simple_type = write_element(&write_simple_chars);
 
               let serialize = quote! {
                 writer.set_start_event_name(::std::option::Option::None);
@@ -263,7 +280,8 @@ fn inner_enum_inspector(
 
               let write_sub_type = |data_type| {
                 write_element(match data_type {
-                  Field::FieldString => &write_string_chars,
+                  Field::FieldString { .. } => &write_string_chars,
+                  Field::FieldBool => &write_simple_chars,
                   _ => &serialize,
                 })
               };
@@ -305,7 +323,7 @@ fn inner_enum_inspector(
                      write_element(&match_field(&serialize))
                    }
                 }
-                Field::FieldString => match_field(&write_element(&write_string_chars)),
+                Field::FieldString { .. } => match_field(&write_element(&write_string_chars)),
                 _simple_type => match_field(&write_simple_type),
               }
             })
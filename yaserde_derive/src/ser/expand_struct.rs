@@ -24,8 +24,20 @@ pub fn serialize(
       if field.is_attribute() {
         let label_name = field.renamed_label(root_attributes);
 
+        if let Some(serialize_with) = field.get_serialize_with_function() {
+          return serialize_attribute_with(&field, &label_name, &serialize_with);
+        }
+
+        let value_to_string = |value: TokenStream| {
+          if field.is_bool() {
+            field.ser_bool(value, root_attributes)
+          } else {
+            quote!(#value.to_string())
+          }
+        };
+
         match field.get_type() {
-          Field::FieldString
+          Field::FieldString { .. }
           | Field::FieldBool
           | Field::FieldI8
           | Field::FieldU8
@@ -37,13 +49,13 @@ pub fn serialize(
           | Field::FieldU64
           | Field::FieldF32
           | Field::FieldF64 => field.ser_wrap_default_attribute(
-            Some(quote!(self.#label.to_string())),
+            Some(value_to_string(quote!(self.#label))),
             quote!({
               struct_start_event.attr(#label_name, &yaserde_inner)
             }),
           ),
           Field::FieldOption { data_type } => match *data_type {
-            Field::FieldString => field.ser_wrap_default_attribute(
+            Field::FieldString { .. } => field.ser_wrap_default_attribute(
               None,
               quote!({
                 if let ::std::option::Option::Some(ref value) = self.#label {
@@ -63,18 +75,22 @@ pub fn serialize(
             | Field::FieldI64
             | Field::FieldU64
             | Field::FieldF32
-            | Field::FieldF64 => field.ser_wrap_default_attribute(
-              Some(
-                quote!(self.#label.map_or_else(|| ::std::string::String::new(), |v| v.to_string())),
-              ),
-              quote!({
-                if let ::std::option::Option::Some(ref value) = self.#label {
-                  struct_start_event.attr(#label_name, &yaserde_inner)
-                } else {
-                  struct_start_event
-                }
-              }),
-            ),
+            | Field::FieldF64 => {
+              let to_string = value_to_string(quote!(v));
+
+              field.ser_wrap_default_attribute(
+                Some(
+                  quote!(self.#label.map_or_else(|| ::std::string::String::new(), |v| #to_string)),
+                ),
+                quote!({
+                  if let ::std::option::Option::Some(ref value) = self.#label {
+                    struct_start_event.attr(#label_name, &yaserde_inner)
+                  } else {
+                    struct_start_event
+                  }
+                }),
+              )
+            }
             Field::FieldVec { .. } => {
               let item_ident = Ident::new("yaserde_item", field.get_span());
               let inner = enclose_formatted_characters(&item_ident, label_name);
@@ -91,14 +107,7 @@ pub fn serialize(
               )
             }
             Field::FieldStruct { .. } => field.ser_wrap_default_attribute(
-              Some(quote! {
-              self.#label
-                .as_ref()
-                .map_or_else(
-                  || ::std::result::Result::Ok(::std::string::String::new()),
-                  |v| ::yaserde::ser::to_string_content(v),
-                )?
-              }),
+              Some(quote!(::yaserde::ser::to_attribute_value(self.#label.iter(), "")?)),
               quote!({
                 if let ::std::option::Option::Some(ref yaserde_struct) = self.#label {
                   struct_start_event.attr(#label_name, &yaserde_inner)
@@ -110,14 +119,45 @@ pub fn serialize(
             Field::FieldOption { .. } => unimplemented!(),
           },
           Field::FieldStruct { .. } => field.ser_wrap_default_attribute(
-            Some(quote! { ::yaserde::ser::to_string_content(&self.#label)? }),
+            Some(quote! {
+              ::yaserde::ser::to_attribute_value(::std::iter::once(&self.#label), "")?
+            }),
             quote!({
               struct_start_event.attr(#label_name, &yaserde_inner)
             }),
           ),
-          Field::FieldVec { .. } => {
-            // TODO
-            quote!()
+          Field::FieldVec { data_type } => {
+            let separator = field.get_separator();
+
+            let to_attribute_value = match *data_type {
+              Field::FieldStruct { .. } => {
+                quote!(::yaserde::ser::to_attribute_value(self.#label.iter(), #separator)?)
+              }
+              // Reported by `YaSerdeField::check`
+              Field::FieldOption { .. } | Field::FieldVec { .. } => unreachable!(),
+              _ => {
+                let item_to_string = value_to_string(quote!(yaserde_item));
+
+                quote! {{
+                  let mut yaserde_items = ::std::vec::Vec::new();
+                  for yaserde_item in self.#label.iter() {
+                    yaserde_items.push(#item_to_string);
+                  }
+                  yaserde_items.join(#separator)
+                }}
+              }
+            };
+
+            field.ser_wrap_default_attribute(
+              Some(to_attribute_value),
+              quote!({
+                if yaserde_inner.is_empty() {
+                  struct_start_event
+                } else {
+                  struct_start_event.attr(#label_name, &yaserde_inner)
+                }
+              }),
+            )
           }
         }
       } else {
@@ -126,7 +166,7 @@ pub fn serialize(
             quote!(
               let (attributes, namespace) = self.#label.serialize_attributes(
                 ::std::vec![],
-                ::yaserde::__xml::namespace::Namespace::empty(),
+                ::yaserde::events::Namespace::empty(),
               )?;
               child_attributes_namespace.extend(&namespace);
               child_attributes.extend(attributes);
@@ -138,44 +178,179 @@ pub fn serialize(
     })
     .collect();
 
+  let has_empty_elements = data_struct
+    .fields
+    .iter()
+    .any(|field| YaSerdeField::new(field.clone()).has_empty_element());
+
+  let write_leading_comments: TokenStream = data_struct
+    .fields
+    .iter()
+    .map(|field| YaSerdeField::new(field.clone()))
+    .filter(|field| field.is_comments())
+    .map(|field| {
+      let label = field.label();
+      quote! {
+        for comment in &self.#label {
+          writer
+            .write(::yaserde::events::WriteEvent::comment(comment))
+            .map_err(|e| e.to_string())?;
+        }
+      }
+    })
+    .collect();
+
   let struct_inspector: TokenStream = data_struct
     .fields
     .iter()
     .map(|field| YaSerdeField::new(field.clone()))
-    .filter(|field| !field.is_attribute())
+    .filter(|field| !field.is_attribute() && !field.is_comments())
     .filter_map(|field| {
       let label = field.label();
       if field.is_text_content() {
+        if let Some(serialize_with) = field.get_serialize_with_function() {
+          return Some(serialize_text_with(&field, &serialize_with));
+        }
+
         return match field.get_type() {
           Field::FieldOption { .. } => Some(quote!(
             let s = self.#label.as_deref().unwrap_or_default();
-            let data_event = ::yaserde::__xml::writer::XmlEvent::characters(s);
+            let data_event = ::yaserde::events::WriteEvent::characters(s);
             writer.write(data_event).map_err(|e| e.to_string())?;
           )),
+          Field::FieldVec { .. } => {
+            let separator = field.get_separator();
+            let to_string = if field.is_bool() {
+              field.ser_bool(quote!(item), root_attributes)
+            } else {
+              quote!(item.to_string())
+            };
+
+            Some(quote!(
+              let s = self.#label
+                .iter()
+                .map(|item| #to_string)
+                .collect::<::std::vec::Vec<_>>()
+                .join(#separator);
+              let data_event = ::yaserde::events::WriteEvent::characters(&s);
+              writer.write(data_event).map_err(|e| e.to_string())?;
+            ))
+          }
+          Field::FieldBool => {
+            let to_string = field.ser_bool(quote!(self.#label), root_attributes);
+
+            Some(quote!(
+              let s = #to_string;
+              let data_event = ::yaserde::events::WriteEvent::characters(&s);
+              writer.write(data_event).map_err(|e| e.to_string())?;
+            ))
+          }
           _ => Some(quote!(
-            let data_event = ::yaserde::__xml::writer::XmlEvent::characters(&self.#label);
+            let data_event = ::yaserde::events::WriteEvent::characters(&self.#label);
             writer.write(data_event).map_err(|e| e.to_string())?;
           )),
         };
       }
       let label_name = field.renamed_label(root_attributes);
       let conditions = condition_generator(&label, &field);
+      let conditions = if has_empty_elements {
+        let empty_element = field.ser_empty_element();
+        quote!(#empty_element #conditions)
+      } else {
+        conditions
+      };
+
+      let serialize_items = |inner: TokenStream| match field.get_type() {
+        Field::FieldOption { .. } => Some(quote! {
+          #conditions {
+            if let ::std::option::Option::Some(ref yaserde_item) = self.#label {
+              #inner
+            }
+          }
+        }),
+        Field::FieldVec { .. } => Some(quote! {
+          #conditions {
+            for yaserde_item in self.#label.iter() {
+              #inner
+            }
+          }
+        }),
+        _ => Some(quote! {
+          #conditions {
+            let yaserde_item = &self.#label;
+            #inner
+          }
+        }),
+      };
+
+      if field.is_presence() {
+        return Some(quote! {
+          #conditions {
+            if self.#label {
+              let start_event = ::yaserde::events::WriteEvent::start_element(#label_name);
+              writer.write(start_event).map_err(|e| e.to_string())?;
+              let end_event = ::yaserde::events::WriteEvent::end_element();
+              writer.write(end_event).map_err(|e| e.to_string())?;
+            }
+          }
+        });
+      }
+
+      match field.get_nullable_type() {
+        None | Some(Field::FieldStruct { .. }) => {}
+        Some(_) => {
+          let item_ident = Ident::new("yaserde_item", field.get_span());
+          let inner = enclose_formatted_characters_for_value(&item_ident, label_name.clone());
+
+          return Some(quote! {
+            #conditions {
+              match self.#label {
+                ::yaserde::Nullable::Absent => {}
+                ::yaserde::Nullable::Empty => {
+                  let start_event = ::yaserde::events::WriteEvent::start_element(#label_name);
+                  writer.write(start_event).map_err(|e| e.to_string())?;
+                  let end_event = ::yaserde::events::WriteEvent::end_element();
+                  writer.write(end_event).map_err(|e| e.to_string())?;
+                }
+                ::yaserde::Nullable::Value(ref yaserde_item) => {
+                  #inner
+                }
+              }
+            }
+          });
+        }
+      }
+
+      if let Some(serialize_with) = field.get_serialize_with_function() {
+        return serialize_items(enclose_serialized_characters(
+          quote!(yaserde_item),
+          label_name,
+          &serialize_with,
+        ));
+      }
+
+      if field.is_bool() {
+        return serialize_items(enclose_string_characters(
+          field.ser_bool(quote!(yaserde_item), root_attributes),
+          label_name,
+        ));
+      }
 
       if field.is_cdata() {
         return quote! {
             #conditions {
-              let start_event = ::yaserde::__xml::writer::XmlEvent::start_element(#label_name);
+              let start_event = ::yaserde::events::WriteEvent::start_element(#label_name);
               writer.write(start_event).map_err(|e| e.to_string())?;
-              let data = ::yaserde::__xml::writer::events::XmlEvent::cdata(&self.#label);
+              let data = ::yaserde::events::WriteEvent::cdata(&self.#label);
               writer.write(data).map_err(|e| e.to_string())?;
-              let end_event = ::yaserde::__xml::writer::XmlEvent::end_element();
+              let end_event = ::yaserde::events::WriteEvent::end_element();
               writer.write(end_event).map_err(|e| e.to_string())?;
             }
         }.into()
       }
 
       match field.get_type() {
-        Field::FieldString
+        Field::FieldString { .. }
         | Field::FieldBool
         | Field::FieldI8
         | Field::FieldU8
@@ -189,7 +364,7 @@ pub fn serialize(
         | Field::FieldF64 => serialize_element(&label, label_name, &conditions),
 
         Field::FieldOption { data_type } => match *data_type {
-          Field::FieldString
+          Field::FieldString { .. }
           | Field::FieldBool
           | Field::FieldI8
           | Field::FieldU8
@@ -268,7 +443,7 @@ pub fn serialize(
           })
         }
         Field::FieldVec { data_type } => match *data_type {
-          Field::FieldString => {
+          Field::FieldString { .. } => {
             let item_ident = Ident::new("yaserde_item", field.get_span());
             let inner = enclose_formatted_characters_for_value(&item_ident, label_name);
 
@@ -355,12 +530,103 @@ pub fn serialize(
     })
     .collect();
 
+  let struct_inspector = if has_empty_elements {
+    quote!(#struct_inspector writer.set_empty_element(::std::option::Option::None);)
+  } else {
+    struct_inspector
+  };
+
   implement_serializer(
     name,
     root,
     root_attributes,
     append_attributes,
+    write_leading_comments,
     struct_inspector,
     generics,
   )
 }
+
+fn serialize_attribute_with(
+  field: &YaSerdeField,
+  label_name: &str,
+  serialize_with: &TokenStream,
+) -> TokenStream {
+  let label = field.label();
+
+  match field.get_type() {
+    Field::FieldOption { .. } => field.ser_wrap_default_attribute(
+      Some(quote! {
+        self.#label
+          .as_ref()
+          .map(|yaserde_item| #serialize_with(yaserde_item))
+          .transpose()?
+      }),
+      quote!({
+        if let ::std::option::Option::Some(ref value) = yaserde_inner {
+          struct_start_event.attr(#label_name, value)
+        } else {
+          struct_start_event
+        }
+      }),
+    ),
+    Field::FieldVec { .. } => {
+      let separator = field.get_separator();
+
+      field.ser_wrap_default_attribute(
+        Some(quote! {{
+          let mut yaserde_items = ::std::vec::Vec::new();
+          for yaserde_item in self.#label.iter() {
+            yaserde_items.push(#serialize_with(yaserde_item)?);
+          }
+          yaserde_items.join(#separator)
+        }}),
+        quote!({
+          if yaserde_inner.is_empty() {
+            struct_start_event
+          } else {
+            struct_start_event.attr(#label_name, &yaserde_inner)
+          }
+        }),
+      )
+    }
+    _ => field.ser_wrap_default_attribute(
+      Some(quote!(#serialize_with(&self.#label)?)),
+      quote!({
+        struct_start_event.attr(#label_name, &yaserde_inner)
+      }),
+    ),
+  }
+}
+
+fn serialize_text_with(field: &YaSerdeField, serialize_with: &TokenStream) -> TokenStream {
+  let label = field.label();
+
+  let content = match field.get_type() {
+    Field::FieldOption { .. } => quote! {
+      self.#label
+        .as_ref()
+        .map(|yaserde_item| #serialize_with(yaserde_item))
+        .transpose()?
+        .unwrap_or_default()
+    },
+    Field::FieldVec { .. } => {
+      let separator = field.get_separator();
+
+      quote! {{
+        let mut yaserde_items = ::std::vec::Vec::new();
+        for yaserde_item in self.#label.iter() {
+          yaserde_items.push(#serialize_with(yaserde_item)?);
+        }
+        yaserde_items.join(#separator)
+      }}
+    }
+    _ => quote!(#serialize_with(&self.#label)?),
+  };
+
+  quote! {
+    let s = #content;
+    let data_event = ::yaserde::events::WriteEvent::characters(&s);
+    writer.write(data_event).map_err(|e| e.to_string())?;
+  }
+}
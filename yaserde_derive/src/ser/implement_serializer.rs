@@ -10,6 +10,7 @@ pub fn implement_serializer(
   root: &str,
   attributes: &YaSerdeAttribute,
   append_attributes: TokenStream,
+  leading_comments: TokenStream,
   inner_inspector: TokenStream,
   generics: &Generics,
 ) -> TokenStream {
@@ -28,22 +29,24 @@ pub fn implement_serializer(
         let skip = writer.skip_start_end();
 
         if !#flatten && !skip {
+          #leading_comments
+
           let mut child_attributes = ::std::vec![];
-          let mut child_attributes_namespace = ::yaserde::__xml::namespace::Namespace::empty();
+          let mut child_attributes_namespace = ::yaserde::events::Namespace::empty();
 
           let yaserde_label = writer.get_start_event_name().unwrap_or_else(|| #root.to_string());
           let struct_start_event =
-            ::yaserde::__xml::writer::XmlEvent::start_element(yaserde_label.as_ref()) #namespaces_definition;
+            ::yaserde::events::WriteEvent::start_element(yaserde_label.as_ref()) #namespaces_definition;
           #append_attributes
 
-          let event: ::yaserde::__xml::writer::events::XmlEvent = struct_start_event.into();
+          let event: ::yaserde::events::WriteEvent = struct_start_event.into();
 
-          if let ::yaserde::__xml::writer::events::XmlEvent::StartElement {
+          if let ::yaserde::events::WriteEvent::StartElement {
             name,
             attributes,
             namespace,
           } = event {
-            let mut attributes: ::std::vec::Vec<::yaserde::__xml::attribute::OwnedAttribute> =
+            let mut attributes: ::std::vec::Vec<::yaserde::events::OwnedAttribute> =
               attributes.into_owned().to_vec().iter().map(|k| k.to_owned()).collect();
             attributes.extend(child_attributes);
 
@@ -52,7 +55,7 @@ pub fn implement_serializer(
             let mut all_namespaces = namespace.into_owned();
             all_namespaces.extend(&child_attributes_namespace);
 
-            writer.write(::yaserde::__xml::writer::events::XmlEvent::StartElement{
+            writer.write(::yaserde::events::WriteEvent::StartElement{
               name,
               attributes: ::std::borrow::Cow::Owned(all_attributes),
               namespace: ::std::borrow::Cow::Owned(all_namespaces)
@@ -65,7 +68,7 @@ pub fn implement_serializer(
         #inner_inspector
 
         if !#flatten && !skip {
-          let struct_end_event = ::yaserde::__xml::writer::XmlEvent::end_element();
+          let struct_end_event = ::yaserde::events::WriteEvent::end_element();
           writer.write(struct_end_event).map_err(|e| e.to_string())?;
         }
 
@@ -74,27 +77,27 @@ pub fn implement_serializer(
 
       fn serialize_attributes(
         &self,
-        mut source_attributes: ::std::vec::Vec<::yaserde::__xml::attribute::OwnedAttribute>,
-        mut source_namespace: ::yaserde::__xml::namespace::Namespace,
+        mut source_attributes: ::std::vec::Vec<::yaserde::events::OwnedAttribute>,
+        mut source_namespace: ::yaserde::events::Namespace,
       ) -> ::std::result::Result<
-        (::std::vec::Vec<::yaserde::__xml::attribute::OwnedAttribute>, ::yaserde::__xml::namespace::Namespace),
+        (::std::vec::Vec<::yaserde::events::OwnedAttribute>, ::yaserde::events::Namespace),
         ::std::string::String
       > {
-        let mut child_attributes = ::std::vec::Vec::<::yaserde::__xml::attribute::OwnedAttribute>::new();
-        let mut child_attributes_namespace = ::yaserde::__xml::namespace::Namespace::empty();
+        let mut child_attributes = ::std::vec::Vec::<::yaserde::events::OwnedAttribute>::new();
+        let mut child_attributes_namespace = ::yaserde::events::Namespace::empty();
 
         let struct_start_event =
-          ::yaserde::__xml::writer::XmlEvent::start_element("temporary_element_to_generate_attributes")
+          ::yaserde::events::WriteEvent::start_element("temporary_element_to_generate_attributes")
           #namespaces_definition;
 
         #append_attributes
-        let event: ::yaserde::__xml::writer::events::XmlEvent = struct_start_event.into();
+        let event: ::yaserde::events::WriteEvent = struct_start_event.into();
 
-        if let ::yaserde::__xml::writer::events::XmlEvent::StartElement { attributes, namespace, .. } = event {
+        if let ::yaserde::events::WriteEvent::StartElement { attributes, namespace, .. } = event {
           source_namespace.extend(&namespace.into_owned());
           source_namespace.extend(&child_attributes_namespace);
 
-          let a: ::std::vec::Vec<::yaserde::__xml::attribute::OwnedAttribute> =
+          let a: ::std::vec::Vec<::yaserde::events::OwnedAttribute> =
             attributes.into_owned().to_vec().iter().map(|k| k.to_owned()).collect();
           source_attributes.extend(a);
           source_attributes.extend(child_attributes);
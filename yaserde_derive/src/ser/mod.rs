@@ -5,11 +5,13 @@ pub mod implement_serializer;
 pub mod label;
 pub mod namespace;
 
-use crate::common::YaSerdeAttribute;
+use crate::common::{self, YaSerdeAttribute};
 use proc_macro2::TokenStream;
 use quote::quote;
 
-pub fn expand_derive_serialize(ast: &syn::DeriveInput) -> Result<TokenStream, String> {
+pub fn expand_derive_serialize(ast: &syn::DeriveInput) -> syn::Result<TokenStream> {
+  common::check(ast)?;
+
   let name = &ast.ident;
   let attrs = &ast.attrs;
   let data = &ast.data;